@@ -0,0 +1,2504 @@
+use futures::runtime::{
+    block_on, spawn, wake_at, Context, FutureRunner, PollRunner, Priority, SimpleRunner, Waker,
+};
+use futures::{
+    abortable, from_callback, join_all_limited, join_all_settled, lazy, loop_fn, maybe_done,
+    now_or_never, pending, poll_fn, ready, remote_handle, select_all, select_ok, spawn_blocking,
+    yield_now, zip, AndThen, BackoffPolicy, BoxFuture, CallbackHandle, CancellationToken, Catch,
+    CatchUnwind, Chain, CircuitBreaker, CircuitState, ContractViolation, Debounce, Delay, Done,
+    Either, ErrInto, ErrorKind, ExponentialBackoff, Failed, FixedBackoff, Fuse, FutError,
+    FutResult, FutState, Future, FutureCache, FutureExt, FuturesOrdered, Inspect, InspectErr, Join,
+    Join3, Join4, Loop, Map, MapErr, MapInto, MaybeDone, Never, OrElse, Race, RateLimiter, Retry,
+    Select, Shared, SingleFlight, Timeout, TryFlatten, TryJoin, TryJoin3, WeakShared,
+};
+use log::debug;
+use std::any::type_name;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt::Debug;
+use std::mem;
+use std::panic;
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+pub fn test_simple_runner() -> Result<(), FutError> {
+    let mut runner = SimpleRunner::new();
+    runner.schedule(Done::new(42));
+
+    let future_chain = Chain::new(Done::new(10), |x| Done::new(x + 5));
+    runner.schedule(future_chain);
+    runner.run()?;
+
+    debug!("Simple runner completed successfully");
+
+    Ok(())
+}
+
+pub fn test_poll_runner() -> Result<(), FutError> {
+    let mut runner = PollRunner::new();
+
+    runner.schedule(Done::new(1));
+    runner.schedule(Done::new(2));
+
+    let complex_chain = Chain::new(Done::new(3), |x| {
+        Chain::new(Done::new(x + 1), |y| Done::new(y * 2))
+    });
+
+    runner.schedule(complex_chain);
+    runner.run()?;
+
+    debug!("Poll runner completed successfully");
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct TestTracker {
+    execution_order: Vec<String>,
+    results: Vec<usize>,
+}
+
+impl TestTracker {
+    pub fn track_exec_order(&mut self, step: &str) {
+        self.execution_order.push(step.to_string());
+    }
+
+    pub fn track_result(&mut self, res: usize) {
+        self.results.push(res);
+    }
+}
+
+#[derive(Debug)]
+struct TrackDone<T> {
+    inner: Done<T>,
+    tracker: Rc<RefCell<TestTracker>>,
+    id: String,
+}
+
+impl<T: Debug> TrackDone<T> {
+    pub fn new(val: T, tracker: Rc<RefCell<TestTracker>>, id: &str) -> Self {
+        tracker
+            .borrow_mut()
+            .track_exec_order(&format!("Creating {}", id));
+
+        Self {
+            inner: Done::new(val),
+            tracker,
+            id: id.to_string(),
+        }
+    }
+}
+
+impl TrackDone<usize> {
+    fn track_result(&self, res: usize) {
+        self.tracker.borrow_mut().track_result(res);
+    }
+}
+
+impl Future for TrackDone<usize> {
+    type Output = usize;
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        self.tracker
+            .borrow_mut()
+            .track_exec_order(&format!("Polling {}", self.id));
+        match self.inner.poll()? {
+            FutResult {
+                state: FutState::Done,
+                value: Some(val),
+            } => {
+                self.track_result(val);
+                Ok(FutResult::finished(val))
+            }
+            other => Ok(other),
+        }
+    }
+
+    fn cleanup(&mut self) {
+        self.tracker
+            .borrow_mut()
+            .track_exec_order(&format!("Destroying {}", self.id));
+        self.inner.cleanup();
+    }
+}
+
+pub fn test_sequential_execution() -> Result<(), FutError> {
+    let tracker = Rc::new(RefCell::new(TestTracker::default()));
+    let mut runner = PollRunner::new();
+
+    let fut1 = TrackDone::new(5, Rc::clone(&tracker), "Future1");
+    let fut2 = TrackDone::new(10, Rc::clone(&tracker), "Future2");
+
+    runner.schedule(fut1);
+    runner.schedule(fut2);
+    runner.run()?;
+
+    let tracker = tracker.borrow();
+    debug!("Execution order: {:?}", tracker.execution_order);
+    debug!("Results: {:?}", tracker.results);
+
+    assert_eq!(tracker.results, vec![5, 10]);
+
+    assert!(tracker
+        .execution_order
+        .contains(&"Creating Future1".to_string()));
+
+    assert!(tracker
+        .execution_order
+        .contains(&"Creating Future2".to_string()));
+
+    assert!(tracker
+        .execution_order
+        .contains(&"Polling Future1".to_string()));
+
+    assert!(tracker
+        .execution_order
+        .contains(&"Polling Future2".to_string()));
+
+    Ok(())
+}
+
+pub fn test_chained_futures() -> Result<(), FutError> {
+    let tracker = Rc::new(RefCell::new(TestTracker::default()));
+    let mut runner = PollRunner::new();
+
+    let initial = TrackDone::new(5, Rc::clone(&tracker), "Initial");
+    let tracker_clone = Rc::clone(&tracker);
+    let chain = Chain::new(initial, move |x| {
+        TrackDone::new(x * 2, Rc::clone(&tracker_clone), "Chained")
+    });
+
+    runner.schedule(chain);
+    runner.run()?;
+
+    let tracker = tracker.borrow();
+    debug!("Chain execution order: {:?}", tracker.execution_order);
+    debug!("Chain results: {:?}", tracker.results);
+
+    assert_eq!(tracker.results.last(), Some(&10));
+
+    Ok(())
+}
+
+pub fn test_polled_after_completion() -> Result<(), FutError> {
+    let mut exhausted = Done::new(1);
+    exhausted.poll()?;
+
+    let mut simple = SimpleRunner::new();
+    let terminated = simple.schedule(exhausted.clone());
+    simple.schedule(Done::new(2));
+    simple.run()?;
+    assert!(simple.is_empty());
+    assert!(matches!(
+        terminated.try_result(),
+        Err(FutError::PolledAfterCompletion)
+    ));
+
+    let mut runner = PollRunner::new();
+    runner.schedule(exhausted);
+    runner.schedule(Chain::new(Done::new(3), |x| Done::new(x + 1)));
+    runner.run()?;
+    assert!(runner.is_empty());
+
+    Ok(())
+}
+
+pub fn test_take_result() -> Result<(), FutError> {
+    let mut runner = PollRunner::new();
+    let first = runner.schedule(Done::new(7_usize));
+    let chained = runner.schedule(Chain::new(Done::new(4_usize), |x| Done::new(x * 3)));
+    runner.run()?;
+
+    assert!(matches!(
+        runner.take_result::<String>(first.id()),
+        Err(FutError::ResultTypeMismatch { task, .. }) if task == first.id()
+    ));
+    assert_eq!(runner.take_result::<usize>(first.id())?, 7);
+    assert_eq!(runner.take_result::<usize>(chained.id())?, 12);
+    assert!(matches!(
+        runner.take_result::<usize>(first.id()),
+        Err(FutError::ResultUnavailable(_))
+    ));
+
+    let mut runner = PollRunner::new();
+    let dropped = runner.schedule(Done::new(5_usize)).id();
+    let spawner = runner.schedule(lazy(|| {
+        let _ = spawn(Done::new(6_usize));
+        8_usize
+    }));
+    runner.run()?;
+
+    assert!(matches!(
+        runner.take_result::<usize>(dropped),
+        Err(FutError::ResultUnavailable(_))
+    ));
+    assert!(matches!(
+        runner.take_result::<usize>(spawner.id() + 1),
+        Err(FutError::ResultUnavailable(_))
+    ));
+    assert_eq!(runner.take_result::<usize>(spawner.id())?, 8);
+
+    Ok(())
+}
+
+#[derive(Debug)]
+struct ContractBreaker {
+    state: FutState,
+}
+
+impl Future for ContractBreaker {
+    type Output = usize;
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        Ok(FutResult {
+            state: self.state,
+            value: None,
+        })
+    }
+
+    fn cleanup(&mut self) {}
+}
+
+pub fn test_strict_mode() -> Result<(), FutError> {
+    let mut exhausted = Done::new(1);
+    exhausted.poll()?;
+
+    let mut runner = PollRunner::new().with_strict_mode();
+    let after_completion = runner.schedule(exhausted);
+    let without_value = runner.schedule(ContractBreaker {
+        state: FutState::Done,
+    });
+    let without_wake = runner.schedule(ContractBreaker {
+        state: FutState::Waiting,
+    });
+    let mut cleaned = Done::new(7);
+    cleaned.cleanup();
+    let cleaned_twice = runner.schedule(cleaned.clone());
+    let mut rejected = Failed::_new(FutError::TimedOut);
+    let cleaned_in_poll = runner.schedule(poll_fn(move || {
+        rejected.cleanup();
+        rejected.cleanup();
+        Ok::<_, FutError>(FutResult::finished(8))
+    }));
+    let healthy = runner.schedule(Done::new(5));
+    runner.run()?;
+
+    for (task, expected) in [
+        (after_completion, ContractViolation::PolledAfterCompletion),
+        (without_value, ContractViolation::DoneWithoutValue),
+        (without_wake, ContractViolation::WaitingWithoutWakeSource),
+        (cleaned_twice, ContractViolation::CleanupCalledTwice),
+        (cleaned_in_poll, ContractViolation::CleanupCalledTwice),
+    ] {
+        match task.try_result() {
+            Err(FutError::ContractViolation {
+                task: reported,
+                violation,
+            }) => {
+                assert_eq!(reported, task.id());
+                assert_eq!(violation, expected);
+            }
+            other => panic!("expected contract violation, got {:?}", other),
+        }
+    }
+    assert_eq!(healthy.try_result()?, 5);
+
+    let mut lenient = PollRunner::new();
+    let cleaned_twice = lenient.schedule(cleaned);
+    let stranded = lenient.schedule(ContractBreaker {
+        state: FutState::Waiting,
+    });
+    lenient.run()?;
+    assert_eq!(cleaned_twice.try_result()?, 7);
+    assert!(matches!(
+        stranded.try_result(),
+        Err(FutError::SleepingUnsupported)
+    ));
+
+    Ok(())
+}
+
+pub fn test_map() -> Result<(), FutError> {
+    let tracker = Rc::new(RefCell::new(TestTracker::default()));
+    let mut runner = PollRunner::new();
+
+    let mapped = Map::new(TrackDone::new(6, Rc::clone(&tracker), "Mapped"), |x| x * 7);
+    let chained = Map::new(Chain::new(Done::new(1), |x| Done::new(x + 1)), |x| x * 10);
+    let mapped = runner.schedule(mapped);
+    let chained = runner.schedule(chained);
+    runner.run()?;
+
+    assert_eq!(mapped.try_result()?, 42);
+    assert_eq!(chained.try_result()?, 20);
+    assert!(tracker
+        .borrow()
+        .execution_order
+        .contains(&"Destroying Mapped".to_string()));
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AppError {
+    Fut(String),
+    Rejected(&'static str),
+}
+
+impl From<FutError> for AppError {
+    fn from(err: FutError) -> Self {
+        Self::Fut(format!("{:?}", err))
+    }
+}
+
+pub fn test_map_err() -> Result<(), FutError> {
+    let mut rejected = MapErr::new(Failed::_new(FutError::TimedOut), AppError::from);
+    assert_eq!(
+        rejected.poll().err(),
+        Some(AppError::from(FutError::TimedOut))
+    );
+
+    let mut exhausted = Failed::_new(AppError::Rejected("bad input"));
+    assert_eq!(
+        exhausted.poll().err(),
+        Some(AppError::Rejected("bad input"))
+    );
+    assert_eq!(
+        exhausted.poll().err(),
+        Some(AppError::from(FutError::PolledAfterCompletion))
+    );
+
+    let mut chain = Chain::new(MapErr::new(Done::new(2), AppError::from), |x| {
+        Map::new(MapErr::new(Done::new(x), AppError::from), |y| y + 1)
+    });
+    assert!(chain.poll().is_ok());
+    let res = chain.poll().expect("chain of Done futures should not fail");
+    assert_eq!(res.value, Some(3));
+
+    Ok(())
+}
+
+pub fn test_and_then() -> Result<(), FutError> {
+    let tracker = Rc::new(RefCell::new(TestTracker::default()));
+    let mut runner = PollRunner::new();
+
+    let tracker_clone = Rc::clone(&tracker);
+    let handle = runner.schedule(AndThen::new(
+        TrackDone::new(4, Rc::clone(&tracker), "First"),
+        move |x| TrackDone::new(x + 1, tracker_clone, "Second"),
+    ));
+    runner.run()?;
+    assert_eq!(handle.try_result()?, 5);
+    assert!(tracker
+        .borrow()
+        .execution_order
+        .contains(&"Destroying First".to_string()));
+
+    let transformed = Rc::new(RefCell::new(false));
+    let transformed_clone = Rc::clone(&transformed);
+    let mut failing = AndThen::new(Failed::_new(AppError::Rejected("nope")), move |_| {
+        *transformed_clone.borrow_mut() = true;
+        MapErr::new(Done::new(1), AppError::from)
+    });
+    assert_eq!(failing.poll().err(), Some(AppError::Rejected("nope")));
+    assert!(!*transformed.borrow());
+
+    Ok(())
+}
+
+#[derive(Debug)]
+struct SleepOnce {
+    slept: bool,
+    value: usize,
+}
+
+impl Future for SleepOnce {
+    type Output = usize;
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        if self.slept {
+            return Ok(FutResult::finished(self.value));
+        }
+
+        self.slept = true;
+        Ok(FutResult {
+            state: FutState::Waiting,
+            value: Some(self.value),
+        })
+    }
+
+    fn cleanup(&mut self) {}
+}
+
+pub fn test_idle_busy_callbacks() -> Result<(), FutError> {
+    let transitions = Rc::new(RefCell::new(Vec::new()));
+    let mut runner = PollRunner::new();
+
+    let idle = Rc::clone(&transitions);
+    runner.on_idle(move || idle.borrow_mut().push("idle"));
+    let busy = Rc::clone(&transitions);
+    runner.on_busy(move || busy.borrow_mut().push("busy"));
+
+    runner.schedule(SleepOnce {
+        slept: false,
+        value: 1,
+    });
+    runner.run()?;
+
+    assert_eq!(*transitions.borrow(), vec!["busy", "idle", "busy", "idle"]);
+
+    Ok(())
+}
+
+pub fn test_or_else() -> Result<(), FutError> {
+    let mut recovered = OrElse::new(Failed::_new(AppError::Rejected("primary down")), |err| {
+        assert_eq!(err, AppError::Rejected("primary down"));
+        MapErr::new(Done::new(()), AppError::from)
+    });
+    let switched = recovered
+        .poll()
+        .expect("failure should switch to the fallback");
+    assert_eq!(switched.state, FutState::Pending);
+    let res = recovered
+        .poll()
+        .expect("fallback should recover the failure");
+    assert_eq!(res.state, FutState::Done);
+
+    let mut runner = PollRunner::new();
+    let untouched = runner.schedule(OrElse::new(Done::new(8), |_| Done::new(0)));
+    runner.run()?;
+    assert_eq!(untouched.try_result()?, 8);
+
+    Ok(())
+}
+
+pub fn test_join_all_settled() -> Result<(), FutError> {
+    let mut exhausted = Done::new(0);
+    exhausted.poll()?;
+
+    let mut settled = join_all_settled(vec![Done::new(1), exhausted, Done::new(3)]);
+    let results = settled.poll()?.value.expect("future should have resolved");
+
+    assert_eq!(results.len(), 3);
+    assert!(matches!(results[0], Ok(1)));
+    assert!(matches!(results[1], Err(FutError::PolledAfterCompletion)));
+    assert!(matches!(results[2], Ok(3)));
+
+    Ok(())
+}
+
+pub fn test_join() -> Result<(), FutError> {
+    let tracker = Rc::new(RefCell::new(TestTracker::default()));
+    let mut runner = PollRunner::new();
+
+    let slow = Chain::new(TrackDone::new(2, Rc::clone(&tracker), "Slow"), |x| {
+        Done::new(x * 10)
+    });
+    let fast = TrackDone::new(3, Rc::clone(&tracker), "Fast");
+    let handle = runner.schedule(Map::new(Join::new(slow, fast), |(a, b)| a + b));
+    runner.run()?;
+
+    assert_eq!(handle.try_result()?, 23);
+    let tracker = tracker.borrow();
+    let polled = |id: &str| {
+        tracker
+            .execution_order
+            .iter()
+            .position(|step| step == &format!("Polling {}", id))
+    };
+    assert!(polled("Slow") < polled("Fast"));
+    assert!(tracker
+        .execution_order
+        .contains(&"Destroying Fast".to_string()));
+
+    Ok(())
+}
+
+pub fn test_join3_join4() -> Result<(), FutError> {
+    let mut runner = PollRunner::new();
+
+    let three = runner.schedule(Map::new(
+        Join3::new(
+            Done::new(1),
+            Chain::new(Done::new(2), |x| Done::new(x * 2)),
+            Done::new(3),
+        ),
+        |(a, b, c)| a + b + c,
+    ));
+    let four = runner.schedule(Map::new(
+        Join4::new(Done::new(1), Done::new(2), Done::new(3), Done::new(4)),
+        |(a, b, c, d)| a * b * c * d,
+    ));
+    runner.run()?;
+
+    assert_eq!(three.try_result()?, 8);
+    assert_eq!(four.try_result()?, 24);
+
+    Ok(())
+}
+
+pub fn test_reserve() -> Result<(), FutError> {
+    let mut runner = PollRunner::new();
+    runner.reserve(64);
+    let reserved = runner.capacity();
+    assert!(reserved.tasks >= 64);
+    assert!(reserved.results >= 64);
+
+    for i in 0..64 {
+        runner.schedule(Done::new(i));
+    }
+    runner.run()?;
+    assert_eq!(runner.capacity(), reserved);
+
+    let mut simple = SimpleRunner::new();
+    simple.reserve(8);
+    assert!(simple.capacity().tasks >= 8);
+
+    Ok(())
+}
+
+pub fn test_start_shutdown_hooks() -> Result<(), FutError> {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let mut runner = PollRunner::new();
+
+    let start = Rc::clone(&events);
+    runner.on_start(Map::new(Done::new(()), move |()| {
+        start.borrow_mut().push("start")
+    }));
+    let shutdown = Rc::clone(&events);
+    runner.on_shutdown(Map::new(Done::new(()), move |()| {
+        shutdown.borrow_mut().push("shutdown")
+    }));
+    let task = Rc::clone(&events);
+    runner.schedule(Map::new(Done::new(1), move |x| {
+        task.borrow_mut().push("task");
+        x
+    }));
+    runner.run()?;
+
+    assert_eq!(*events.borrow(), vec!["start", "task", "shutdown"]);
+
+    let mut runner = PollRunner::new();
+    let delayed = Rc::clone(&events);
+    runner.on_start(Map::new(Delay::new(Duration::from_millis(10)), move |()| {
+        delayed.borrow_mut().push("delayed start")
+    }));
+    let notified = Rc::clone(&events);
+    runner.on_shutdown(Map::new(
+        from_callback(|handle| {
+            thread::spawn(move || handle.complete(()));
+        }),
+        move |()| notified.borrow_mut().push("notified shutdown"),
+    ));
+    runner.run()?;
+    assert_eq!(events.borrow()[3..], ["delayed start", "notified shutdown"]);
+
+    let mut simple = SimpleRunner::new();
+    let shut_down = Rc::new(Cell::new(false));
+    let shutdown = Rc::clone(&shut_down);
+    simple.on_shutdown(lazy(move || shutdown.set(true)));
+    simple.schedule(ContractBreaker {
+        state: FutState::Waiting,
+    });
+    assert!(matches!(simple.run(), Err(FutError::SleepingUnsupported)));
+    assert!(shut_down.get());
+
+    Ok(())
+}
+
+pub fn test_select() -> Result<(), FutError> {
+    let tracker = Rc::new(RefCell::new(TestTracker::default()));
+
+    let tracker_clone = Rc::clone(&tracker);
+    let slow = Chain::new(Done::new(1), move |x| {
+        TrackDone::new(x + 1, tracker_clone, "Slow")
+    });
+    let fast = TrackDone::new(5, Rc::clone(&tracker), "Fast");
+    let mut select = Select::new(slow, fast);
+    let destroyed = |id: &str| {
+        tracker
+            .borrow()
+            .execution_order
+            .contains(&format!("Destroying {}", id))
+    };
+
+    match select.poll()?.value {
+        Some(Either::Right((winner, mut loser))) => {
+            assert_eq!(winner, 5);
+            assert!(!destroyed("Slow"));
+            assert!(!destroyed("Fast"));
+            assert_eq!(loser.poll()?.value, Some(2));
+            loser.cleanup();
+        }
+        other => panic!(
+            "expected the second future to win, got {:?}",
+            other.is_some()
+        ),
+    }
+    select.cleanup();
+    assert!(destroyed("Fast"));
+    assert!(matches!(
+        select.poll(),
+        Err(FutError::PolledAfterCompletion)
+    ));
+
+    let mut select = Select::new(Done::new(1), Done::new(2));
+    let Some(Either::Left((1, loser))) = select.poll()?.value else {
+        panic!("expected the first future to win");
+    };
+    let mut runner = PollRunner::new().with_strict_mode();
+    let rescheduled = runner.schedule(loser);
+    runner.run()?;
+    assert_eq!(rescheduled.try_result()?, 2);
+    select.cleanup();
+
+    Ok(())
+}
+
+pub fn test_select_all() -> Result<(), FutError> {
+    let responder = |x: usize| Map::new(Chain::new(Done::new(x), Done::new), |x| x * 100);
+    let mut race = select_all(vec![responder(1), responder(2)]).biased();
+
+    assert_eq!(race.poll()?.state, FutState::Pending);
+    let (winner, index, remaining) = race.poll()?.value.expect("future should have resolved");
+    assert_eq!(winner, 100);
+    assert_eq!(index, 0);
+    assert_eq!(remaining.len(), 1);
+
+    let mut rest = select_all(remaining);
+    let (winner, index, remaining) = rest.poll()?.value.expect("future should have resolved");
+    assert_eq!((winner, index, remaining.len()), (200, 0, 0));
+
+    Ok(())
+}
+
+pub fn test_race() -> Result<(), FutError> {
+    let tracker = Rc::new(RefCell::new(TestTracker::default()));
+    let mut runner = PollRunner::new();
+
+    let slow = Chain::new(TrackDone::new(1, Rc::clone(&tracker), "Slow"), |x| {
+        Done::new(x + 1)
+    });
+    let fast = TrackDone::new(7, Rc::clone(&tracker), "Fast");
+    let handle = runner.schedule(Race::new(slow, fast));
+    runner.run()?;
+
+    assert_eq!(handle.try_result()?, 7);
+    assert_eq!(tracker.borrow().results, vec![1, 7]);
+
+    Ok(())
+}
+
+pub fn test_try_join() -> Result<(), FutError> {
+    let tracker = Rc::new(RefCell::new(TestTracker::default()));
+
+    let mut exhausted = Done::new(0);
+    exhausted.poll()?;
+    let tracker_clone = Rc::clone(&tracker);
+    let sibling = Chain::new(Done::new(1), move |x| {
+        TrackDone::new(x + 1, tracker_clone, "Sibling")
+    });
+    let mut failing = TryJoin::new(sibling, exhausted);
+
+    assert!(matches!(
+        failing.poll(),
+        Err(FutError::PolledAfterCompletion)
+    ));
+    assert!(matches!(
+        failing.poll(),
+        Err(FutError::PolledAfterCompletion)
+    ));
+    assert!(tracker
+        .borrow()
+        .execution_order
+        .contains(&"Destroying Sibling".to_string()));
+
+    let mut runner = PollRunner::new();
+    let handle = runner.schedule(Map::new(
+        TryJoin3::new(Done::new(1), Done::new(2), Done::new(3)),
+        |(a, b, c)| a + b + c,
+    ));
+    runner.run()?;
+    assert_eq!(handle.try_result()?, 6);
+
+    Ok(())
+}
+
+#[derive(Debug)]
+struct Spawner {
+    children: usize,
+    spawned: Rc<RefCell<Vec<usize>>>,
+}
+
+impl Future for Spawner {
+    type Output = usize;
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        for child in 0..self.children {
+            let spawned = Rc::clone(&self.spawned);
+            spawn(Map::new(Done::new(child), move |x| {
+                spawned.borrow_mut().push(x);
+                x
+            }))?;
+        }
+
+        Ok(FutResult::finished(self.children))
+    }
+
+    fn cleanup(&mut self) {}
+}
+
+pub fn test_spawn_from_poll() -> Result<(), FutError> {
+    assert!(matches!(spawn(Done::new(0)), Err(FutError::NoActiveRunner)));
+
+    let spawned = Rc::new(RefCell::new(Vec::new()));
+    let mut runner = PollRunner::new();
+    runner.schedule(Spawner {
+        children: 3,
+        spawned: Rc::clone(&spawned),
+    });
+    runner.run()?;
+    assert_eq!(*spawned.borrow(), vec![0, 1, 2]);
+
+    let mut simple = SimpleRunner::new();
+    simple.schedule(Spawner {
+        children: 2,
+        spawned: Rc::clone(&spawned),
+    });
+    simple.run()?;
+    assert_eq!(spawned.borrow().len(), 5);
+
+    Ok(())
+}
+
+pub fn test_fuse() -> Result<(), FutError> {
+    let mut fused = Fuse::new(Chain::new(Done::new(1), |x| Done::new(x + 1)));
+
+    assert_eq!(fused.poll()?.state, FutState::Pending);
+    assert!(!fused.is_terminated());
+    assert_eq!(fused.poll()?.value, Some(2));
+    assert!(fused.is_terminated());
+
+    for _ in 0..3 {
+        let res = fused.poll()?;
+        assert_eq!(res.state, FutState::Pending);
+        assert_eq!(res.value, None);
+    }
+
+    Ok(())
+}
+
+pub fn test_inspect_err() -> Result<(), FutError> {
+    let failures = Rc::new(RefCell::new(0));
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    let counter = Rc::clone(&failures);
+    let mut failing = InspectErr::new(Failed::_new(AppError::Rejected("timeout")), move |_| {
+        *counter.borrow_mut() += 1
+    });
+    assert_eq!(failing.poll().err(), Some(AppError::Rejected("timeout")));
+
+    let mut runner = PollRunner::new();
+    let values = Rc::clone(&seen);
+    let counter = Rc::clone(&failures);
+    let handle = runner.schedule(InspectErr::new(
+        Inspect::new(Done::new(3), move |x| values.borrow_mut().push(*x)),
+        move |_| *counter.borrow_mut() += 1,
+    ));
+    runner.run()?;
+
+    assert_eq!(handle.try_result()?, 3);
+    assert_eq!(*seen.borrow(), vec![3]);
+    assert_eq!(*failures.borrow(), 1);
+
+    Ok(())
+}
+
+pub fn test_catch() -> Result<(), FutError> {
+    let mut runner = SimpleRunner::new();
+
+    let recovered = runner.schedule(Catch::new(
+        Map::new(Failed::_new(AppError::Rejected("flaky")), |()| 1),
+        |err| match err {
+            AppError::Rejected(_) => 0,
+            AppError::Fut(_) => usize::MAX,
+        },
+    ));
+    let healthy = runner.schedule(Catch::new(MapErr::new(Done::new(5), AppError::from), |_| 0));
+    runner.run()?;
+
+    assert_eq!(recovered.try_result()?, 0);
+    assert_eq!(healthy.try_result()?, 5);
+
+    Ok(())
+}
+
+pub fn test_either() -> Result<(), FutError> {
+    let mut runner = PollRunner::new();
+
+    let branch = |x: usize| {
+        Chain::new(Done::new(x), |x| {
+            if x % 2 == 0 {
+                Either::Left(Done::new(x / 2))
+            } else {
+                Either::Right(Chain::new(Done::new(x), |x| Done::new(3 * x + 1)))
+            }
+        })
+    };
+    let even = runner.schedule(branch(8));
+    let odd = runner.schedule(branch(3));
+    runner.run()?;
+
+    assert_eq!(even.try_result()?, 4);
+    assert_eq!(odd.try_result()?, 10);
+
+    Ok(())
+}
+
+pub fn test_lazy() -> Result<(), FutError> {
+    let computed = Rc::new(RefCell::new(false));
+    let mut runner = PollRunner::new();
+
+    let flag = Rc::clone(&computed);
+    let handle = runner.schedule(lazy(move || {
+        *flag.borrow_mut() = true;
+        6 * 7
+    }));
+    assert!(!*computed.borrow());
+
+    runner.run()?;
+    assert!(*computed.borrow());
+    assert_eq!(handle.try_result()?, 42);
+
+    Ok(())
+}
+
+pub fn test_poll_fn() -> Result<(), FutError> {
+    let mut runner = PollRunner::new();
+
+    let mut countdown = 3;
+    let handle = runner.schedule(poll_fn(move || {
+        if countdown == 0 {
+            return Ok(FutResult::finished(99));
+        }
+
+        countdown -= 1;
+        Ok(FutResult::pending())
+    }));
+    runner.run()?;
+
+    assert_eq!(handle.try_result()?, 99);
+
+    Ok(())
+}
+
+pub fn test_find_tasks() -> Result<(), FutError> {
+    let mut runner = PollRunner::new();
+    let first = runner.schedule(Done::new(1));
+    let second = runner.schedule(Done::new(2));
+
+    let all = runner.find_tasks(|_| true);
+    assert_eq!(
+        all.iter().map(|info| info.id).collect::<Vec<_>>(),
+        vec![first.id(), second.id()]
+    );
+    assert!(all.iter().all(|info| info.state == FutState::Pending));
+
+    let only_second = runner.find_tasks(|info| info.id == second.id());
+    assert_eq!(only_second.len(), 1);
+    assert!(runner
+        .find_tasks(|info| info.state == FutState::Waiting)
+        .is_empty());
+    assert!(runner
+        .find_tasks(|info| info.age > Duration::from_secs(3600))
+        .is_empty());
+
+    runner.run()?;
+    assert!(runner.find_tasks(|_| true).is_empty());
+
+    let mut simple = SimpleRunner::new();
+    let sleeper = simple.schedule(Delay::new(Duration::from_secs(60)));
+    let stalled = simple.schedule(ContractBreaker {
+        state: FutState::Waiting,
+    });
+    assert!(matches!(simple.run(), Err(FutError::SleepingUnsupported)));
+    let states = simple
+        .find_tasks(|_| true)
+        .iter()
+        .map(|info| (info.id, info.state))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        states,
+        vec![
+            (sleeper.id(), FutState::Waiting),
+            (stalled.id(), FutState::Pending)
+        ]
+    );
+
+    Ok(())
+}
+
+pub fn test_ready_and_pending() -> Result<(), FutError> {
+    struct Opaque(usize);
+
+    let mut opaque = ready(Opaque(11));
+    let value = opaque.poll()?.value.expect("future should have resolved");
+    assert_eq!(value.0, 11);
+    assert!(matches!(
+        opaque.poll(),
+        Err(FutError::PolledAfterCompletion)
+    ));
+
+    let mut never = pending::<usize>();
+    for _ in 0..3 {
+        assert_eq!(never.poll()?.state, FutState::Pending);
+    }
+
+    let mut runner = PollRunner::new();
+    let handle = runner.schedule(Race::new(pending(), ready(4)));
+    runner.run()?;
+    assert_eq!(handle.try_result()?, 4);
+
+    let polls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&polls);
+    let mut parked = pending::<usize>();
+    let handle = runner.schedule(
+        poll_fn(move || {
+            counter.set(counter.get() + 1);
+            let polled = parked.poll()?;
+            assert_eq!(polled.state, FutState::Waiting);
+            Ok::<FutResult<usize>, FutError>(polled)
+        })
+        .timeout(Duration::from_millis(5)),
+    );
+    runner.run()?;
+    assert!(matches!(handle.try_result(), Err(FutError::TimedOut)));
+    assert!(polls.get() <= 2, "pending was polled {} times", polls.get());
+
+    Ok(())
+}
+
+pub fn test_error_kinds() -> Result<(), FutError> {
+    assert_eq!(FutError::PolledAfterCompletion.kind(), ErrorKind::Protocol);
+    assert!(!FutError::PolledAfterCompletion.is_retryable());
+    assert_eq!(FutError::NoActiveRunner.kind(), ErrorKind::Resource);
+    assert!(FutError::NoActiveRunner.is_retryable());
+    assert_eq!(FutError::ResultUnavailable(0).kind(), ErrorKind::Internal);
+
+    Ok(())
+}
+
+pub fn test_shared() -> Result<(), FutError> {
+    let computations = Rc::new(RefCell::new(0));
+    let mut runner = PollRunner::new();
+
+    let counter = Rc::clone(&computations);
+    let upstream = Shared::new(Chain::new(Done::new(20), move |x| {
+        *counter.borrow_mut() += 1;
+        Done::new(x + 1)
+    }));
+
+    let doubled = runner.schedule(Map::new(upstream.clone(), |x| x * 2));
+    let tripled = runner.schedule(Map::new(upstream.clone(), |x| x * 3));
+    let raw = runner.schedule(upstream);
+    runner.run()?;
+
+    assert_eq!(doubled.try_result()?, 42);
+    assert_eq!(tripled.try_result()?, 63);
+    assert_eq!(raw.try_result()?, 21);
+    assert_eq!(*computations.borrow(), 1);
+
+    let reading = Shared::new(from_callback(|handle: CallbackHandle<usize>| {
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            handle.complete(5);
+        });
+    }));
+    let mut runner = PollRunner::new();
+    let first = runner.schedule(reading.clone().map(|x| x + 1));
+    let second = runner.schedule(reading.map(|x| x + 2));
+    runner.run()?;
+
+    assert_eq!(first.try_result()?, 6);
+    assert_eq!(second.try_result()?, 7);
+
+    Ok(())
+}
+
+pub fn test_timeout() -> Result<(), FutError> {
+    let fast = block_on(Timeout::new(Done::new(7), Duration::from_secs(1)))?;
+    assert_eq!(fast, 7);
+
+    let spinning = block_on(Timeout::new(pending::<usize>(), Duration::from_millis(5)));
+    assert!(matches!(spinning, Err(FutError::TimedOut)));
+
+    let polls = Rc::new(RefCell::new(0));
+    let counter = Rc::clone(&polls);
+    let started = Instant::now();
+    let waiting = block_on(Timeout::new(
+        poll_fn(move || {
+            *counter.borrow_mut() += 1;
+            Ok::<FutResult<usize>, FutError>(FutResult {
+                state: FutState::Waiting,
+                value: None,
+            })
+        }),
+        Duration::from_millis(20),
+    ));
+
+    assert!(matches!(waiting, Err(FutError::TimedOut)));
+    assert!(started.elapsed() >= Duration::from_millis(20));
+    assert!(*polls.borrow() <= 3, "waiting future was busy-polled");
+    assert_eq!(FutError::TimedOut.kind(), ErrorKind::Timeout);
+    assert!(FutError::TimedOut.is_retryable());
+
+    Ok(())
+}
+
+pub fn test_delay() -> Result<(), FutError> {
+    let started = Instant::now();
+    let mut runner = PollRunner::new();
+    let short = runner.schedule(Map::new(Delay::new(Duration::from_millis(10)), |_| 1));
+    let long = runner.schedule(Chain::new(Delay::new(Duration::from_millis(20)), |_| {
+        Done::new(2)
+    }));
+    runner.run()?;
+
+    assert_eq!(short.try_result()?, 1);
+    assert_eq!(long.try_result()?, 2);
+    assert!(started.elapsed() >= Duration::from_millis(20));
+
+    let started = Instant::now();
+    let mut simple = SimpleRunner::new();
+    let delayed = simple.schedule(Map::new(Delay::new(Duration::from_millis(10)), |_| 3));
+    simple.run()?;
+
+    assert_eq!(delayed.try_result()?, 3);
+    assert!(started.elapsed() >= Duration::from_millis(10));
+
+    let mut delay = Delay::new(Duration::ZERO);
+    assert_eq!(delay.poll()?.state, FutState::Done);
+    assert!(matches!(delay.poll(), Err(FutError::PolledAfterCompletion)));
+
+    Ok(())
+}
+
+pub fn test_retry() -> Result<(), FutError> {
+    let attempts = Rc::new(RefCell::new(0));
+    let counter = Rc::clone(&attempts);
+    let recovered = block_on(Retry::new(
+        move || {
+            *counter.borrow_mut() += 1;
+            let attempt = *counter.borrow();
+            poll_fn(move || match attempt {
+                1 | 2 => Err(FutError::TimedOut),
+                _ => Ok(FutResult::finished(attempt)),
+            })
+        },
+        FixedBackoff::new(Duration::from_millis(1), 5),
+    ))?;
+    assert_eq!(recovered, 3);
+    assert_eq!(*attempts.borrow(), 3);
+
+    let attempts = Rc::new(RefCell::new(0));
+    let counter = Rc::clone(&attempts);
+    let started = Instant::now();
+    let exhausted = block_on(Retry::new(
+        move || {
+            *counter.borrow_mut() += 1;
+            poll_fn(|| Err::<FutResult<usize>, _>(FutError::TimedOut))
+        },
+        ExponentialBackoff::new(Duration::from_millis(2), 3),
+    ));
+    assert!(matches!(exhausted, Err(FutError::TimedOut)));
+    assert_eq!(*attempts.borrow(), 3);
+    assert!(started.elapsed() >= Duration::from_millis(6));
+
+    let attempts = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&attempts);
+    let rejected = block_on(Retry::new(
+        move || {
+            counter.set(counter.get() + 1);
+            poll_fn(|| Err::<FutResult<usize>, _>(FutError::Aborted))
+        },
+        FixedBackoff::new(Duration::from_millis(1), 5),
+    ));
+    assert!(matches!(rejected, Err(FutError::Aborted)));
+    assert_eq!(attempts.get(), 1);
+
+    let attempts = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&attempts);
+    let persistent = block_on(Retry::new_with_predicate(
+        move || {
+            counter.set(counter.get() + 1);
+            poll_fn(|| Err::<FutResult<usize>, _>(FutError::Aborted))
+        },
+        FixedBackoff::new(Duration::from_millis(1), 3),
+        |err: &FutError| matches!(err, FutError::Aborted),
+    ));
+    assert!(matches!(persistent, Err(FutError::Aborted)));
+    assert_eq!(attempts.get(), 3);
+
+    let mut policy =
+        ExponentialBackoff::new(Duration::from_millis(10), 5).max_delay(Duration::from_millis(30));
+    let delays: Vec<_> = (1..=5)
+        .map(|failures| policy.next_delay(failures))
+        .collect();
+    assert_eq!(
+        delays,
+        vec![
+            Some(Duration::from_millis(10)),
+            Some(Duration::from_millis(20)),
+            Some(Duration::from_millis(30)),
+            Some(Duration::from_millis(30)),
+            None,
+        ]
+    );
+
+    Ok(())
+}
+
+pub fn test_shutdown_signal() -> Result<(), FutError> {
+    let mut runner = PollRunner::new();
+    let signal = runner.shutdown_signal();
+
+    let accepted = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&accepted);
+    let accept_loop = runner.schedule(Race::new(
+        poll_fn(move || {
+            counter.set(counter.get() + 1);
+            Ok::<FutResult<usize>, FutError>(FutResult::pending())
+        }),
+        Map::new(signal.clone(), |()| 0),
+    ));
+
+    let trigger = signal.clone();
+    runner.schedule(Chain::new(
+        Delay::new(Duration::from_millis(5)),
+        move |_| {
+            trigger.trigger();
+            Done::new(1)
+        },
+    ));
+
+    let observed = Rc::new(Cell::new(false));
+    let hook_signal = runner.shutdown_signal();
+    let seen = Rc::clone(&observed);
+    runner.on_shutdown(lazy(move || seen.set(hook_signal.is_triggered())));
+    runner.run()?;
+
+    assert!(signal.is_triggered());
+    assert!(observed.get());
+    assert!(accepted.get() > 0);
+    assert_eq!(accept_loop.try_result()?, 0);
+
+    let mut runner = PollRunner::new();
+    let signal = runner.shutdown_signal();
+    let polls = Rc::new(Cell::new(0));
+    let waiter = runner.schedule(count_polls(&polls, signal.clone().map(|()| 3)));
+    runner.schedule(Delay::new(Duration::from_millis(10)).map(move |()| signal.trigger()));
+    runner.run()?;
+    assert_eq!(waiter.try_result()?, 3);
+    assert_eq!(polls.get(), 2);
+
+    let mut runner = PollRunner::new().with_shutdown_after(Duration::from_millis(50));
+    let signal = runner.shutdown_signal();
+    let started = Instant::now();
+    let interrupted = runner.schedule(Race::new(
+        Delay::new(Duration::from_secs(10)).map(|()| 1),
+        signal.clone().map(|()| 0),
+    ));
+    runner.run()?;
+    assert_eq!(interrupted.try_result()?, 0);
+    assert!(started.elapsed() < Duration::from_secs(1));
+
+    let finished = runner.schedule(Race::new(
+        Delay::new(Duration::from_millis(1)).map(|()| 1),
+        signal.clone().map(|()| 0),
+    ));
+    runner.run()?;
+    assert_eq!(finished.try_result()?, 1);
+    assert!(signal.is_triggered());
+
+    let mut simple = SimpleRunner::new().with_shutdown_after(Duration::from_millis(10));
+    assert!(!simple.shutdown_signal().is_triggered());
+    let interrupted = simple.schedule(Race::new(
+        Delay::new(Duration::from_secs(10)).map(|()| 1),
+        simple.shutdown_signal().map(|()| 0),
+    ));
+    simple.run()?;
+    assert_eq!(interrupted.try_result()?, 0);
+    assert!(started.elapsed() < Duration::from_secs(1));
+
+    Ok(())
+}
+
+pub fn test_yield_now() -> Result<(), FutError> {
+    let order = Rc::new(RefCell::new(Vec::new()));
+    let mut runner = PollRunner::new();
+
+    let yielding = Rc::clone(&order);
+    runner.schedule(Map::new(yield_now(), move |()| {
+        yielding.borrow_mut().push("yielding");
+        1
+    }));
+    let eager = Rc::clone(&order);
+    runner.schedule(Map::new(ready(()), move |()| {
+        eager.borrow_mut().push("eager");
+        2
+    }));
+    runner.run()?;
+
+    assert_eq!(*order.borrow(), vec!["eager", "yielding"]);
+
+    let mut yielded = yield_now();
+    assert_eq!(yielded.poll()?.state, FutState::Pending);
+    assert_eq!(yielded.poll()?.state, FutState::Done);
+    assert!(matches!(
+        yielded.poll(),
+        Err(FutError::PolledAfterCompletion)
+    ));
+
+    Ok(())
+}
+
+pub fn test_loop_fn() -> Result<(), FutError> {
+    let sum = block_on(loop_fn((0, 1), |(total, n)| {
+        ready(if n > 10 {
+            Loop::Break(total)
+        } else {
+            Loop::Continue((total + n, n + 1))
+        })
+    }))?;
+    assert_eq!(sum, 55);
+
+    let iterations = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&iterations);
+    let mut failing = loop_fn(0, move |n| {
+        counter.set(counter.get() + 1);
+        poll_fn(move || match n {
+            3 => Err(AppError::Rejected("third iteration")),
+            n => Ok(FutResult {
+                state: FutState::Done,
+                value: Some(Loop::<(), usize>::Continue(n + 1)),
+            }),
+        })
+    });
+
+    let err = loop {
+        match failing.poll() {
+            Ok(res) => assert_eq!(res.state, FutState::Pending),
+            Err(e) => break e,
+        }
+    };
+    assert_eq!(err, AppError::Rejected("third iteration"));
+    assert_eq!(iterations.get(), 4);
+
+    Ok(())
+}
+
+pub fn test_future_ext() -> Result<(), FutError> {
+    let chained = block_on(
+        Done::new(3)
+            .then(|x| Done::new(x + 1))
+            .map(|x| x * 2)
+            .inspect(|x| debug!("Chained value {}", x)),
+    )?;
+    assert_eq!(chained, 8);
+
+    let boxed: Vec<BoxFuture<usize, FutError>> = vec![
+        Done::new(1).boxed(),
+        Done::new(2).map(|x| x * 10).boxed(),
+        ready(()).and_then(|()| Done::new(300)).boxed(),
+    ];
+    let mut runner = PollRunner::new();
+    let handles: Vec<_> = boxed.into_iter().map(|fut| runner.schedule(fut)).collect();
+    runner.run()?;
+    let values = handles
+        .into_iter()
+        .map(|handle| handle.try_result())
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(values, vec![1, 20, 300]);
+
+    let joined = block_on(Done::new(4).join(Done::new(5)).map(|(a, b)| a * b))?;
+    assert_eq!(joined, 20);
+
+    let raced = block_on(pending().race(Done::new(6)))?;
+    assert_eq!(raced, 6);
+
+    let mut recovered = Failed::_new(AppError::Rejected("boom"))
+        .map(|()| 0)
+        .or_else(|_| MapErr::new(Done::new(9), AppError::from));
+    assert_eq!(recovered.poll().map(|res| res.state), Ok(FutState::Pending));
+    assert_eq!(recovered.poll().map(|res| res.value), Ok(Some(9)));
+
+    let mut mixed = MapErr::new(Done::new(2), AppError::from).then_map_err(
+        |x| {
+            poll_fn(move || -> Result<FutResult<usize>, FutError> {
+                match x {
+                    0 => Ok(FutResult::finished(x)),
+                    _ => Err(FutError::TimedOut),
+                }
+            })
+        },
+        AppError::from,
+    );
+    assert!(matches!(
+        mixed.poll(),
+        Ok(FutResult {
+            state: FutState::Pending,
+            ..
+        })
+    ));
+    assert_eq!(
+        mixed.poll().map(|res| res.value),
+        Err(AppError::Fut(format!("{:?}", FutError::TimedOut)))
+    );
+
+    Ok(())
+}
+
+struct NoDebug(usize);
+
+pub fn test_zip() -> Result<(), FutError> {
+    let polls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&polls);
+    let mut zipped = zip(
+        ready(NoDebug(1)),
+        poll_fn(move || {
+            counter.set(counter.get() + 1);
+            Ok::<_, FutError>(match counter.get() {
+                1 | 2 => FutResult {
+                    state: FutState::Pending,
+                    value: None,
+                },
+                n => FutResult {
+                    state: FutState::Done,
+                    value: Some(NoDebug(n)),
+                },
+            })
+        }),
+    );
+
+    assert_eq!(zipped.poll()?.state, FutState::Pending);
+    assert_eq!(zipped.poll()?.state, FutState::Pending);
+    let res = zipped.poll()?;
+    assert_eq!(res.state, FutState::Done);
+    let (left, right) = res.value.expect("future should have resolved");
+    assert_eq!((left.0, right.0), (1, 3));
+    assert_eq!(polls.get(), 3);
+    assert!(matches!(
+        zipped.poll(),
+        Err(FutError::PolledAfterCompletion)
+    ));
+
+    let summed = block_on(Done::new(2).zip(Done::new(5)).map(|(a, b)| a + b))?;
+    assert_eq!(summed, 7);
+
+    Ok(())
+}
+
+pub fn test_single_flight() -> Result<(), FutError> {
+    let lookups = Rc::new(Cell::new(0));
+    let flights: SingleFlight<&str, usize> = SingleFlight::new();
+    let mut runner = PollRunner::new();
+
+    let handles: Vec<_> = (0..3)
+        .map(|_| {
+            let counter = Rc::clone(&lookups);
+            runner.schedule(flights.get("user:1", move || {
+                Delay::new(Duration::from_millis(2)).map(move |()| {
+                    counter.set(counter.get() + 1);
+                    42
+                })
+            }))
+        })
+        .collect();
+    let other = runner.schedule(flights.get("user:2", || Done::new(7)));
+    assert_eq!(flights.in_flight(), 2);
+    runner.run()?;
+
+    for handle in handles {
+        assert_eq!(handle.try_result()?, 42);
+    }
+    assert_eq!(other.try_result()?, 7);
+    assert_eq!(lookups.get(), 1);
+    assert_eq!(flights.in_flight(), 0);
+
+    let counter = Rc::clone(&lookups);
+    let again = block_on(flights.get("user:1", move || {
+        counter.set(counter.get() + 1);
+        Done::new(43)
+    }))?;
+    assert_eq!(again, 43);
+    assert_eq!(lookups.get(), 2);
+
+    Ok(())
+}
+
+pub fn test_err_into() -> Result<(), FutError> {
+    let mut converted: ErrInto<_, AppError> = ErrInto::new(Failed::_new(FutError::TimedOut));
+    assert_eq!(
+        converted.poll().err(),
+        Some(AppError::Fut(format!("{:?}", FutError::TimedOut)))
+    );
+
+    let mut mixed = Done::new(2)
+        .err_into::<AppError>()
+        .and_then(|x| Done::new(x * 3).err_into())
+        .or_else(|_| MapErr::new(Done::new(0), AppError::from));
+    let switched = mixed.poll().expect("converted chain should not fail");
+    assert_eq!(switched.state, FutState::Pending);
+    let res = mixed.poll().expect("converted chain should not fail");
+    assert_eq!(res.value, Some(6));
+
+    Ok(())
+}
+
+pub fn test_never() -> Result<(), FutError> {
+    let mut never = Never;
+    for _ in 0..3 {
+        assert_eq!(never.poll()?.state, FutState::Pending);
+    }
+
+    let value = block_on(Never.select(Done::new(5)).map(|winner| match winner {
+        Either::Left((never, _)) => match never {},
+        Either::Right((value, _)) => value,
+    }))?;
+    assert_eq!(value, 5);
+
+    let timed_out = block_on(
+        Never
+            .map(|never| match never {})
+            .timeout(Duration::from_millis(2)),
+    );
+    assert!(matches!(timed_out, Err(FutError::TimedOut)));
+
+    let polls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&polls);
+    let mut parked = Never;
+    let mut runner = PollRunner::new();
+    let handle = runner.schedule(
+        poll_fn(move || {
+            counter.set(counter.get() + 1);
+            let polled = parked.poll()?;
+            assert_eq!(polled.state, FutState::Waiting);
+            Ok::<FutResult<Infallible>, FutError>(polled)
+        })
+        .map(|never| match never {})
+        .timeout(Duration::from_millis(5)),
+    );
+    runner.run()?;
+    assert!(matches!(handle.try_result(), Err(FutError::TimedOut)));
+    assert!(polls.get() <= 2, "Never was polled {} times", polls.get());
+
+    Ok(())
+}
+
+pub fn test_abortable() -> Result<(), FutError> {
+    let polls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&polls);
+    let (job, handle) = abortable(poll_fn(move || {
+        counter.set(counter.get() + 1);
+        Ok::<FutResult<usize>, FutError>(FutResult::pending())
+    }));
+
+    let mut runner = PollRunner::new();
+    let aborted = runner.schedule(job);
+    let aborter = runner.schedule(Chain::new(
+        Delay::new(Duration::from_millis(2)),
+        move |()| {
+            handle.abort();
+            Done::new(0)
+        },
+    ));
+    let sibling = runner.schedule(Delay::new(Duration::from_millis(5)).map(|()| 9));
+    runner.run()?;
+
+    assert!(matches!(aborted.try_result(), Err(FutError::Aborted)));
+    assert_eq!(aborter.try_result()?, 0);
+    assert_eq!(sibling.try_result()?, 9);
+    assert!(polls.get() > 0);
+
+    let (job, handle) = abortable(Never.map(|never| match never {}));
+    let aborter = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(2));
+        handle.abort();
+    });
+    let res: Result<usize, _> = block_on(job);
+    aborter.join().expect("aborter thread panicked");
+    assert!(matches!(res, Err(FutError::Aborted)));
+    assert_eq!(FutError::Aborted.kind(), ErrorKind::Cancelled);
+
+    let (registered, callbacks) = mpsc::channel();
+    let (job, handle) = abortable(from_callback(move |callback: CallbackHandle<usize>| {
+        let _ = registered.send(callback);
+    }));
+    let aborter = thread::spawn(move || {
+        let callback = callbacks.recv();
+        thread::sleep(Duration::from_millis(5));
+        handle.abort();
+        callback
+    });
+    let parked = block_on(job);
+    let callback = aborter.join();
+    assert!(matches!(parked, Err(FutError::Aborted)));
+    drop(callback);
+
+    let (mut finished, handle) = abortable(Done::new(1));
+    assert_eq!(finished.poll()?.value, Some(1));
+    handle.abort();
+    assert!(matches!(
+        finished.poll(),
+        Err(FutError::PolledAfterCompletion)
+    ));
+
+    Ok(())
+}
+
+pub fn test_cancellation_token() -> Result<(), FutError> {
+    let root = CancellationToken::new();
+    let child = root.child_token();
+    let grandchild = child.child_token();
+    let sibling = root.child_token();
+
+    child.cancel();
+    assert!(child.is_cancelled() && grandchild.is_cancelled());
+    assert!(!root.is_cancelled() && !sibling.is_cancelled());
+
+    let processed = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&processed);
+    let mut runner = PollRunner::new();
+    let worker = runner.schedule(Race::new(
+        poll_fn(move || {
+            counter.set(counter.get() + 1);
+            Ok::<FutResult<usize>, FutError>(FutResult::pending())
+        }),
+        sibling.cancelled().map(|()| 0),
+    ));
+
+    let canceller = root.clone();
+    runner.schedule(Chain::new(
+        Delay::new(Duration::from_millis(2)),
+        move |()| {
+            canceller.cancel();
+            Done::new(1)
+        },
+    ));
+    runner.run()?;
+
+    assert!(sibling.is_cancelled());
+    assert!(processed.get() > 0);
+    assert_eq!(worker.try_result()?, 0);
+
+    let mut cancelled = root.cancelled();
+    assert_eq!(cancelled.poll()?.state, FutState::Done);
+    assert!(matches!(
+        cancelled.poll(),
+        Err(FutError::PolledAfterCompletion)
+    ));
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+struct AppConfig {
+    workers: usize,
+}
+
+pub fn test_runner_extensions() -> Result<(), FutError> {
+    assert!(Context::extension::<AppConfig>().is_none());
+
+    let read_config = || {
+        poll_fn(|| {
+            let workers = Context::extension::<AppConfig>().map_or(0, |config| config.workers);
+            Ok::<_, FutError>(FutResult::finished(workers))
+        })
+    };
+
+    let mut runner = PollRunner::new();
+    assert!(runner.insert_extension(AppConfig { workers: 2 }).is_none());
+    let previous = runner.insert_extension(AppConfig { workers: 4 });
+    assert_eq!(previous.as_deref(), Some(&AppConfig { workers: 2 }));
+    let handle = runner.schedule(read_config());
+    runner.run()?;
+    assert_eq!(handle.try_result()?, 4);
+
+    let mut simple = SimpleRunner::new();
+    simple.insert_extension(AppConfig { workers: 8 });
+    simple.schedule(lazy(move || {
+        let mut nested = PollRunner::new();
+        let handle = nested.schedule(read_config());
+        let nested_result = nested.run().and_then(|()| handle.try_result());
+        assert!(matches!(nested_result, Ok(0)));
+        Context::extension::<AppConfig>().map_or(0, |config| config.workers)
+    }));
+    simple.run()?;
+    assert!(Context::extension::<AppConfig>().is_none());
+
+    let again = simple.schedule(read_config());
+    simple.run()?;
+    assert_eq!(again.try_result()?, 8);
+
+    Ok(())
+}
+
+pub fn test_map_into() -> Result<(), FutError> {
+    let mut widened: MapInto<_, u64> = MapInto::new(Done::new(7u32));
+    assert_eq!(widened.poll()?.value, Some(7u64));
+
+    let total = block_on(
+        Done::new(3u8)
+            .map_into::<usize>()
+            .then(|x| Done::new(x + 1)),
+    )?;
+    assert_eq!(total, 4);
+
+    let mut waiting = MapInto::<_, usize>::new(pending::<u8>());
+    let res = waiting.poll()?;
+    assert_eq!((res.state, res.value), (FutState::Pending, None));
+
+    Ok(())
+}
+
+fn connect(healthy: bool) -> impl Future<Output = Result<Done<usize>, AppError>, Error = FutError> {
+    lazy(move || {
+        if healthy {
+            Ok(Done::new(11))
+        } else {
+            Err(AppError::Rejected("unreachable"))
+        }
+    })
+}
+
+pub fn test_try_flatten() -> Result<(), FutError> {
+    let mut connected = connect(true)
+        .err_into::<AppError>()
+        .map(|res| res.map(|done| done.err_into()))
+        .try_flatten();
+    assert_eq!(connected.poll().map(|res| res.state), Ok(FutState::Pending));
+    assert_eq!(connected.poll().map(|res| res.value), Ok(Some(11)));
+
+    let mut refused = TryFlatten::new(
+        connect(false)
+            .err_into::<AppError>()
+            .map(|res| res.map(|done| done.err_into())),
+    );
+    assert_eq!(
+        refused.poll().err(),
+        Some(AppError::Rejected("unreachable"))
+    );
+    assert!(matches!(refused.poll(), Err(AppError::Fut(_))));
+
+    Ok(())
+}
+
+fn ready_after(polls: usize, value: usize) -> impl Future<Output = usize, Error = FutError> {
+    let mut polled = 0;
+    poll_fn(move || {
+        polled += 1;
+        Ok(if polled >= polls {
+            FutResult::finished(value)
+        } else {
+            FutResult::pending()
+        })
+    })
+}
+
+pub fn test_biased_select() -> Result<(), FutError> {
+    let mut fair = Select::new(ready_after(2, 1), ready_after(2, 2));
+    assert_eq!(fair.poll()?.state, FutState::Pending);
+    assert!(matches!(fair.poll()?.value, Some(Either::Right((2, _)))));
+
+    let mut biased = Select::new(ready_after(2, 1), ready_after(2, 2)).biased();
+    assert_eq!(biased.poll()?.state, FutState::Pending);
+    assert!(matches!(biased.poll()?.value, Some(Either::Left((1, _)))));
+
+    let shutdown = CancellationToken::new();
+    shutdown.cancel();
+    let winner = block_on(Race::new(shutdown.cancelled().map(|()| 0), ready_after(1, 1)).biased())?;
+    assert_eq!(winner, 0);
+
+    let mut fair_all = select_all(vec![
+        ready_after(3, 0),
+        ready_after(3, 1),
+        ready_after(3, 2),
+    ]);
+    assert_eq!(fair_all.poll()?.state, FutState::Pending);
+    assert_eq!(fair_all.poll()?.state, FutState::Pending);
+    let (_, index, _) = fair_all.poll()?.value.expect("future should have resolved");
+    assert_eq!(index, 2);
+
+    let mut biased_all = select_all(vec![
+        ready_after(3, 0),
+        ready_after(3, 1),
+        ready_after(3, 2),
+    ])
+    .biased();
+    assert_eq!(biased_all.poll()?.state, FutState::Pending);
+    assert_eq!(biased_all.poll()?.state, FutState::Pending);
+    let (_, index, _) = biased_all
+        .poll()?
+        .value
+        .expect("future should have resolved");
+    assert_eq!(index, 0);
+
+    Ok(())
+}
+
+fn mirror(attempts: usize, reply: Result<usize, &'static str>) -> BoxFuture<usize, AppError> {
+    let mut polled = 0;
+    poll_fn(move || {
+        polled += 1;
+        if polled < attempts {
+            return Ok(FutResult::pending());
+        }
+        reply.map(FutResult::finished).map_err(AppError::Rejected)
+    })
+    .boxed()
+}
+
+pub fn test_select_ok() -> Result<(), FutError> {
+    let mut fetch = select_ok(vec![
+        mirror(1, Err("primary down")),
+        mirror(3, Ok(30)),
+        mirror(2, Ok(20)),
+    ]);
+    assert_eq!(
+        fetch.poll().ok().map(|res| res.state),
+        Some(FutState::Pending)
+    );
+    assert_eq!(fetch.poll().ok().and_then(|res| res.value), Some(20));
+    assert!(matches!(fetch.poll(), Err(AppError::Fut(_))));
+
+    let mut all_down = select_ok(vec![mirror(2, Err("first")), mirror(1, Err("second"))]);
+    assert_eq!(
+        all_down.poll().ok().map(|res| res.state),
+        Some(FutState::Pending)
+    );
+    assert_eq!(all_down.poll().err(), Some(AppError::Rejected("first")));
+
+    let empty = block_on(select_ok(Vec::<Done<usize>>::new()));
+    assert!(matches!(empty, Err(FutError::CompletedWithoutValue)));
+
+    Ok(())
+}
+
+pub fn test_size_report() -> Result<(), FutError> {
+    let mut quiet = PollRunner::new();
+    quiet.schedule(Done::new(1));
+    assert!(quiet.size_report().is_empty());
+
+    let nested = || {
+        Chain::new(Done::new(1), |x| {
+            Chain::new(Done::new(x), |y| Chain::new(Done::new(y), Done::new))
+        })
+    };
+
+    let mut runner = PollRunner::new().with_size_report();
+    runner.schedule(Done::new(1_usize));
+    runner.schedule(Done::new(2_usize));
+    runner.schedule(nested());
+    runner.run()?;
+
+    let report = runner.size_report();
+    assert_eq!(report.len(), 2);
+    assert!(report[0].type_name.contains("Chain"));
+    assert_eq!(report[0].count, 1);
+    assert_eq!(report[1].type_name, type_name::<Done<usize>>());
+    assert_eq!(report[1].size, mem::size_of::<Done<usize>>());
+    assert_eq!(report[1].count, 2);
+    assert!(report[0].size > report[1].size);
+
+    let mut simple = SimpleRunner::new().with_size_report();
+    simple.schedule(Done::new(3));
+    assert_eq!(simple.size_report()[0].count, 1);
+
+    Ok(())
+}
+
+pub fn test_join_all_limited() -> Result<(), FutError> {
+    let running = Rc::new(Cell::new(0));
+    let peak = Rc::new(Cell::new(0));
+    let job = |id: usize, polls: usize| {
+        let running = Rc::clone(&running);
+        let peak = Rc::clone(&peak);
+        let mut polled = 0;
+        poll_fn(move || {
+            if polled == 0 {
+                running.set(running.get() + 1);
+                peak.set(peak.get().max(running.get()));
+            }
+            polled += 1;
+            if polled < polls {
+                return Ok::<_, FutError>(FutResult::pending());
+            }
+            running.set(running.get() - 1);
+            Ok(FutResult::finished(id * 10))
+        })
+        .boxed()
+    };
+
+    let jobs = vec![job(0, 3), job(1, 1), job(2, 2), job(3, 1), job(4, 2)];
+    let outputs = block_on(join_all_limited(jobs, 2).map(|outputs| outputs.iter().sum::<usize>()))?;
+    assert_eq!(outputs, 100);
+    assert_eq!(peak.get(), 2);
+    assert_eq!(running.get(), 0);
+
+    let mut ordered = join_all_limited(vec![job(1, 2), job(2, 1), job(3, 1)], 2);
+    let res = loop {
+        let res = ordered.poll()?;
+        if res.state == FutState::Done {
+            break res;
+        }
+    };
+    assert_eq!(res.value, Some(vec![10, 20, 30]));
+
+    let failing = vec![
+        poll_fn(|| Ok::<_, AppError>(FutResult::pending())).boxed(),
+        poll_fn(|| Err::<FutResult<usize>, _>(AppError::Rejected("bad job"))).boxed(),
+    ];
+    let mut failing = join_all_limited(failing, 5);
+    assert_eq!(failing.poll().err(), Some(AppError::Rejected("bad job")));
+
+    Ok(())
+}
+
+fn read_sensor_async(on_ready: impl FnOnce(usize) + Send + 'static) {
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(2));
+        on_ready(21);
+    });
+}
+
+pub fn test_from_callback() -> Result<(), FutError> {
+    let reading = block_on(
+        from_callback(|handle: CallbackHandle<usize>| {
+            read_sensor_async(move |value| handle.complete(value))
+        })
+        .map(|value| value * 2),
+    )?;
+    assert_eq!(reading, 42);
+
+    let mut immediate = from_callback(|handle| handle.complete("ready"));
+    assert_eq!(immediate.poll()?.value, Some("ready"));
+    assert!(matches!(
+        immediate.poll(),
+        Err(FutError::PolledAfterCompletion)
+    ));
+
+    let mut abandoned = from_callback(|handle: CallbackHandle<usize>| drop(handle));
+    assert!(matches!(
+        abandoned.poll(),
+        Err(FutError::CompletedWithoutValue)
+    ));
+
+    for _ in 0..1000 {
+        let abandoned = from_callback(|handle: CallbackHandle<usize>| {
+            thread::spawn(move || drop(handle));
+        });
+        assert!(matches!(
+            block_on(abandoned),
+            Err(FutError::CompletedWithoutValue)
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn test_futures_ordered() -> Result<(), FutError> {
+    let mut ordered: FuturesOrdered<_> = [ready_after(3, 1), ready_after(1, 2), ready_after(2, 3)]
+        .into_iter()
+        .collect();
+    assert_eq!(ordered.len(), 3);
+
+    let mut outputs = Vec::new();
+    let mut polls = 0;
+    loop {
+        polls += 1;
+        match ordered.poll_next()? {
+            FutResult {
+                value: Some(Some(value)),
+                ..
+            } => outputs.push(value),
+            FutResult {
+                value: Some(None), ..
+            } => break,
+            FutResult { state, .. } => assert_eq!(state, FutState::Pending),
+        }
+    }
+    assert_eq!(outputs, vec![1, 2, 3]);
+    assert_eq!(polls, 6);
+    assert!(ordered.is_empty());
+
+    let mut with_failure = FuturesOrdered::new();
+    with_failure.push_back(mirror(1, Ok(1)));
+    with_failure.push_back(mirror(1, Err("lost")));
+    with_failure.push_back(mirror(2, Ok(3)));
+    let mut events = Vec::new();
+    loop {
+        match with_failure.poll_next() {
+            Ok(FutResult {
+                value: Some(Some(value)),
+                ..
+            }) => events.push(Ok(value)),
+            Ok(FutResult {
+                value: Some(None), ..
+            }) => break,
+            Ok(_) => {}
+            Err(e) => events.push(Err(e)),
+        }
+    }
+    assert_eq!(events, vec![Err(AppError::Rejected("lost")), Ok(1), Ok(3)]);
+
+    Ok(())
+}
+
+pub fn test_now_or_never() -> Result<(), FutError> {
+    assert_eq!(now_or_never(Done::new(5))?, Some(5));
+    assert_eq!(Done::new(2).map(|x| x + 1).now_or_never()?, Some(3));
+    assert_eq!(pending::<usize>().now_or_never()?, None);
+    assert_eq!(Delay::new(Duration::from_secs(60)).now_or_never()?, None);
+    assert_eq!(
+        now_or_never(Failed::_new(AppError::Rejected("nope"))),
+        Err(AppError::Rejected("nope"))
+    );
+
+    let mut yielded = 0;
+    let still_chaining = Chain::new(Done::new(9), |x| {
+        Inspect::new(Done::new(x + 1), |_| debug!("Second stage polled"))
+    })
+    .inspect(|_| yielded += 1)
+    .now_or_never()?;
+    assert_eq!(still_chaining, None);
+    assert_eq!(yielded, 0);
+
+    Ok(())
+}
+
+pub fn test_maybe_done() -> Result<(), FutError> {
+    let mut slot = maybe_done(ready_after(2, 4));
+    assert!(slot.output_mut().is_none());
+    assert_eq!(slot.poll()?.state, FutState::Pending);
+    assert_eq!(slot.poll()?.state, FutState::Done);
+    assert!(slot.is_done());
+
+    if let Some(output) = slot.output_mut() {
+        *output += 1;
+    }
+    assert_eq!(slot.poll()?.state, FutState::Done);
+    assert_eq!(slot.take_output(), Some(5));
+    assert_eq!(slot.take_output(), None);
+    assert!(matches!(slot, MaybeDone::Gone));
+    assert!(matches!(slot.poll(), Err(FutError::PolledAfterCompletion)));
+
+    let mut unordered = Join3::new(ready_after(3, 1), ready_after(1, 2), ready_after(2, 3));
+    assert_eq!(unordered.poll()?.state, FutState::Pending);
+    assert_eq!(unordered.poll()?.state, FutState::Pending);
+    assert_eq!(unordered.poll()?.value, Some((1, 2, 3)));
+
+    Ok(())
+}
+
+pub fn test_remote_handle() -> Result<(), FutError> {
+    let (remote, handle) = ready_after(3, 12).remote_handle();
+    let mut runner = PollRunner::new();
+    runner.schedule(remote.map(|()| 0));
+    let consumer = runner.schedule(handle.map(|value| value + 1));
+    runner.run()?;
+    assert_eq!(consumer.try_result()?, 13);
+
+    let (remote, handle) = remote_handle(Delay::new(Duration::from_millis(10)).map(|()| 4));
+    let mut runner = PollRunner::new();
+    let polls = Rc::new(Cell::new(0));
+    let consumer = runner.schedule(count_polls(&polls, handle));
+    runner.schedule(remote);
+    runner.run()?;
+    assert_eq!(consumer.try_result()?, 4);
+    assert_eq!(polls.get(), 2);
+
+    let (mut remote, mut handle) = remote_handle(mirror(1, Err("remote failure")));
+    assert_eq!(remote.poll()?.state, FutState::Done);
+    assert_eq!(
+        handle.poll().err(),
+        Some(AppError::Rejected("remote failure"))
+    );
+
+    let polls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&polls);
+    let (mut remote, handle) = remote_handle(poll_fn(move || {
+        counter.set(counter.get() + 1);
+        Ok::<FutResult<usize>, FutError>(FutResult::pending())
+    }));
+    assert_eq!(remote.poll()?.state, FutState::Pending);
+    drop(handle);
+    assert_eq!(remote.poll()?.state, FutState::Done);
+    assert_eq!(polls.get(), 1);
+
+    let (mut remote, handle) = remote_handle(ready_after(2, 1));
+    handle.forget();
+    assert_eq!(remote.poll()?.state, FutState::Pending);
+    assert_eq!(remote.poll()?.state, FutState::Done);
+
+    let (remote, mut handle) = remote_handle(Done::new(1));
+    drop(remote);
+    assert!(matches!(
+        handle.poll(),
+        Err(FutError::CompletedWithoutValue)
+    ));
+
+    Ok(())
+}
+
+pub fn test_catch_unwind() -> Result<(), FutError> {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut runner = PollRunner::new();
+    let exploded = runner.schedule(
+        poll_fn(|| -> Result<FutResult<usize>, FutError> { panic!("sensor offline") })
+            .catch_unwind(),
+    );
+    let survivor = runner.schedule(ready_after(3, 8).catch_unwind());
+    let run = runner.run();
+    panic::set_hook(default_hook);
+    run?;
+
+    assert!(matches!(
+        exploded.try_result(),
+        Err(FutError::Panicked(msg)) if msg == "sensor offline"
+    ));
+    assert_eq!(survivor.try_result()?, 8);
+
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut formatted = CatchUnwind::new(poll_fn(|| -> Result<FutResult<()>, FutError> {
+        panic!("code {}", 7)
+    }));
+    let first = formatted.poll();
+    panic::set_hook(default_hook);
+
+    assert!(matches!(first, Err(FutError::Panicked(msg)) if msg == "code 7"));
+    assert!(matches!(
+        formatted.poll(),
+        Err(FutError::PolledAfterCompletion)
+    ));
+    assert_eq!(
+        FutError::Panicked(String::new()).kind(),
+        ErrorKind::Internal
+    );
+
+    Ok(())
+}
+
+pub fn test_weak_shared() -> Result<(), FutError> {
+    let shared = Shared::new(ready_after(2, 30));
+    let weak = shared.downgrade();
+
+    let mut upgraded = weak.upgrade().expect("shared future is still alive");
+    assert_eq!(upgraded.poll()?.state, FutState::Pending);
+    assert_eq!(upgraded.poll()?.value, Some(30));
+    drop(upgraded);
+
+    let mut late = weak
+        .clone()
+        .upgrade()
+        .expect("shared future is still alive");
+    assert_eq!(late.poll()?.value, Some(30));
+
+    drop(late);
+    drop(shared);
+    assert!(weak.upgrade().is_none());
+
+    let mut cache: HashMap<&str, WeakShared<BoxFuture<usize, FutError>>> = HashMap::new();
+    let lookup = Shared::new(Done::new(1).boxed());
+    cache.insert("abandoned", lookup.downgrade());
+    drop(lookup);
+    cache.retain(|_, weak| weak.upgrade().is_some());
+    assert!(cache.is_empty());
+
+    Ok(())
+}
+
+pub fn test_circuit_breaker() -> Result<(), FutError> {
+    let breaker = CircuitBreaker::new(2, Duration::from_millis(5));
+    let failing = || poll_fn(|| Err::<FutResult<usize>, _>(FutError::TimedOut));
+
+    assert!(matches!(
+        breaker.call(failing()).poll(),
+        Err(FutError::TimedOut)
+    ));
+    assert_eq!(breaker.state(), CircuitState::Closed);
+    assert!(matches!(
+        breaker.call(failing()).poll(),
+        Err(FutError::TimedOut)
+    ));
+    assert_eq!(breaker.state(), CircuitState::Open);
+
+    let calls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&calls);
+    let mut rejected = breaker.call(poll_fn(move || {
+        counter.set(counter.get() + 1);
+        Ok::<_, FutError>(FutResult::finished(1))
+    }));
+    assert!(matches!(rejected.poll(), Err(FutError::CircuitOpen)));
+    assert_eq!(calls.get(), 0);
+    assert!(FutError::CircuitOpen.is_retryable());
+
+    thread::sleep(Duration::from_millis(5));
+    assert_eq!(breaker.state(), CircuitState::HalfOpen);
+    let mut probe = breaker.call(ready_after(2, 3));
+    assert_eq!(probe.poll()?.state, FutState::Pending);
+    assert!(matches!(
+        breaker.call(Done::new(4)).poll(),
+        Err(FutError::CircuitOpen)
+    ));
+    assert_eq!(probe.poll()?.value, Some(3));
+    assert_eq!(breaker.state(), CircuitState::Closed);
+
+    breaker.call(failing()).poll().ok();
+    breaker.call(failing()).poll().ok();
+    thread::sleep(Duration::from_millis(5));
+    assert!(matches!(
+        breaker.call(failing()).poll(),
+        Err(FutError::TimedOut)
+    ));
+    assert_eq!(breaker.state(), CircuitState::Open);
+
+    let recovered = block_on(Retry::new(
+        {
+            let breaker = breaker.clone();
+            move || breaker.call(Done::new(9))
+        },
+        FixedBackoff::new(Duration::from_millis(2), 10),
+    ))?;
+    assert_eq!(recovered, 9);
+    assert_eq!(breaker.state(), CircuitState::Closed);
+
+    Ok(())
+}
+
+pub fn test_future_cache() -> Result<(), FutError> {
+    let computed = Rc::new(Cell::new(0));
+    let mut cache: FutureCache<&str, usize> = FutureCache::new(2);
+    let lookup = |cache: &mut FutureCache<&str, usize>, key, value| {
+        let counter = Rc::clone(&computed);
+        cache.get_or_insert(key, move || {
+            counter.set(counter.get() + 1);
+            ready_after(2, value)
+        })
+    };
+
+    let mut runner = PollRunner::new();
+    let first = runner.schedule(lookup(&mut cache, "a", 1));
+    let second = runner.schedule(lookup(&mut cache, "a", 100));
+    runner.run()?;
+    assert_eq!(first.try_result()?, 1);
+    assert_eq!(second.try_result()?, 1);
+
+    assert_eq!(block_on(lookup(&mut cache, "a", 100))?, 1);
+    assert_eq!(computed.get(), 1);
+
+    assert_eq!(block_on(lookup(&mut cache, "b", 2))?, 2);
+    assert_eq!(block_on(lookup(&mut cache, "a", 100))?, 1);
+    assert_eq!(block_on(lookup(&mut cache, "c", 3))?, 3);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(computed.get(), 3);
+
+    assert_eq!(block_on(lookup(&mut cache, "b", 20))?, 20);
+    assert_eq!(computed.get(), 4);
+
+    let failed = block_on(cache.get_or_insert("d", || {
+        poll_fn(|| Err::<FutResult<usize>, _>(FutError::TimedOut))
+    }));
+    assert!(matches!(failed, Err(FutError::TimedOut)));
+    assert_eq!(block_on(cache.get_or_insert("d", || Done::new(4)))?, 4);
+
+    assert!(cache.remove(&"d"));
+    assert!(!cache.remove(&"d"));
+
+    Ok(())
+}
+
+pub fn test_rate_limiter() -> Result<(), FutError> {
+    let limiter = RateLimiter::new(2, Duration::from_millis(20));
+    let finished = Rc::new(RefCell::new(Vec::new()));
+    let started = Instant::now();
+
+    let mut runner = PollRunner::new();
+    for request in 0..4 {
+        let finished = Rc::clone(&finished);
+        runner.schedule(limiter.limit(lazy(move || {
+            finished.borrow_mut().push((request, started.elapsed()));
+            request
+        })));
+    }
+    runner.run()?;
+
+    let finished = finished.borrow();
+    assert_eq!(finished.len(), 4);
+    assert!(finished[..2]
+        .iter()
+        .all(|(_, at)| *at < Duration::from_millis(10)));
+    assert!(finished[2].1 >= Duration::from_millis(10));
+    assert!(finished[3].1 >= Duration::from_millis(20));
+    assert!(runner.find_tasks(|_| true).is_empty());
+
+    Ok(())
+}
+
+pub fn test_debounce() -> Result<(), FutError> {
+    let settled = Rc::new(Cell::new(None));
+    let started = Instant::now();
+
+    let debounce = Debounce::new(ready(5), Duration::from_millis(15));
+    let handle = debounce.handle();
+    let mut triggers = 0;
+    let mut next_trigger = started + Duration::from_millis(5);
+    let events = poll_fn(move || {
+        if Instant::now() < next_trigger {
+            wake_at(next_trigger);
+            return Ok(FutResult {
+                state: FutState::Waiting,
+                value: None,
+            });
+        }
+
+        handle.trigger();
+        triggers += 1;
+        next_trigger += Duration::from_millis(5);
+        if triggers == 3 {
+            return Ok(FutResult::finished(triggers));
+        }
+        Ok(FutResult::pending())
+    });
+
+    let tracker = Rc::clone(&settled);
+    let mut runner = PollRunner::new();
+    runner.schedule(events);
+    runner.schedule(Map::new(debounce, move |value| {
+        tracker.set(Some(started.elapsed()));
+        value
+    }));
+    runner.run()?;
+
+    let settled = settled.get().expect("Debounce never completed");
+    assert!(settled >= Duration::from_millis(30));
+
+    Ok(())
+}
+
+fn count_polls<F>(
+    polls: &Rc<Cell<usize>>,
+    mut future: F,
+) -> impl Future<Output = usize, Error = FutError>
+where
+    F: Future<Output = usize, Error = FutError>,
+{
+    let polls = Rc::clone(polls);
+    poll_fn(move || {
+        polls.set(polls.get() + 1);
+        future.poll()
+    })
+}
+
+pub fn test_waker() -> Result<(), FutError> {
+    let token = CancellationToken::new();
+    let callback_polls = Rc::new(Cell::new(0));
+    let cancel_polls = Rc::new(Cell::new(0));
+
+    let canceller = token.clone();
+    let reading = from_callback(move |handle: CallbackHandle<usize>| {
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            canceller.cancel();
+            handle.complete(7);
+        });
+    });
+
+    let mut runner = PollRunner::new();
+    let reader = runner.schedule(count_polls(&callback_polls, reading));
+    let watcher = runner.schedule(count_polls(
+        &cancel_polls,
+        token.child_token().cancelled().map(|()| 0),
+    ));
+    runner.run()?;
+
+    assert_eq!(reader.try_result()?, 7);
+    assert_eq!(watcher.try_result()?, 0);
+    assert_eq!(callback_polls.get(), 2);
+    assert_eq!(cancel_polls.get(), 2);
+
+    let wakes = Rc::new(RefCell::new(None));
+    let registered = Rc::clone(&wakes);
+    let waiting = poll_fn(move || {
+        if registered.borrow_mut().take().is_some() {
+            return Ok::<_, FutError>(FutResult::finished(1));
+        }
+
+        *registered.borrow_mut() = Waker::current();
+        Ok(FutResult {
+            state: FutState::Waiting,
+            value: None,
+        })
+    });
+    let mut runner = SimpleRunner::new();
+    runner.schedule(waiting);
+    assert!(matches!(runner.run(), Err(FutError::SleepingUnsupported)));
+    assert!(wakes.borrow().is_none());
+
+    Ok(())
+}
+
+pub fn test_join_handle() -> Result<(), FutError> {
+    let mut runner = PollRunner::new();
+    let producer = runner.schedule(Delay::new(Duration::from_millis(5)).map(|()| 21));
+    assert!(matches!(
+        producer.try_result(),
+        Err(FutError::ResultUnavailable(_))
+    ));
+
+    let consumer_polls = Rc::new(Cell::new(0));
+    let consumer = runner.schedule(count_polls(
+        &consumer_polls,
+        producer.map(|value| value * 2),
+    ));
+    let doubled = runner.schedule(ready(4));
+    runner.run()?;
+
+    assert_eq!(consumer.try_result()?, 42);
+    assert_eq!(doubled.try_result()?, 4);
+    assert_eq!(consumer_polls.get(), 2);
+    assert!(matches!(
+        doubled.try_result(),
+        Err(FutError::ResultUnavailable(id)) if id == doubled.id()
+    ));
+
+    let mut simple = SimpleRunner::new();
+    let chained = simple.schedule(Done::new(1).and_then(|_| Done::new(2)));
+    simple.run()?;
+    assert_eq!(chained.try_result()?, 2);
+    assert!(chained.try_result().is_err());
+
+    Ok(())
+}
+
+pub fn test_generic_outputs() -> Result<(), FutError> {
+    let mut runner = PollRunner::new();
+    let name = runner.schedule(lazy(|| String::from("sensor")));
+    let reading = runner.schedule(ready_after(2, 21).map(|value| (value * 2, "celsius")));
+    let label = runner.schedule(name.map(|name| format!("{}-1", name)));
+    let flushed = runner.schedule(ready(()));
+    runner.run()?;
+
+    assert_eq!(label.try_result()?, "sensor-1");
+    assert_eq!(reading.try_result()?, (42, "celsius"));
+    flushed.try_result()?;
+
+    let mut simple = SimpleRunner::new();
+    let batch = simple.schedule(lazy(|| {
+        spawn(ready(vec![1_u8, 2, 3]))?;
+        Ok::<_, FutError>(vec![4_u8])
+    }));
+    simple.run()?;
+    assert_eq!(batch.try_result()??, vec![4]);
+    assert!(matches!(
+        simple.take_result::<Vec<u8>>(batch.id() + 1),
+        Err(FutError::ResultUnavailable(_))
+    ));
+
+    assert_eq!(block_on(ready("done"))?, "done");
+
+    Ok(())
+}
+
+pub fn test_spawn_blocking() -> Result<(), FutError> {
+    let ticks = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&ticks);
+    let started = Instant::now();
+
+    let mut runner = PollRunner::new();
+    let read_polls = Rc::new(Cell::new(0));
+    let read = runner.schedule(count_polls(
+        &read_polls,
+        spawn_blocking(|| {
+            thread::sleep(Duration::from_millis(20));
+            7
+        }),
+    ));
+    let digest = runner.schedule(spawn_blocking(|| {
+        thread::sleep(Duration::from_millis(20));
+        (1..=10_u64).product::<u64>()
+    }));
+    let heartbeat = runner.schedule(loop_fn(0, move |beats| {
+        counter.set(beats);
+        Delay::new(Duration::from_millis(2)).map(move |()| {
+            if beats < 3 {
+                Loop::Continue(beats + 1)
+            } else {
+                Loop::Break(beats)
+            }
+        })
+    }));
+    runner.run()?;
+
+    assert_eq!(read.try_result()?, 7);
+    assert_eq!(digest.try_result()?, 3_628_800);
+    assert_eq!(heartbeat.try_result()?, 3);
+    assert_eq!(ticks.get(), 3);
+    assert_eq!(read_polls.get(), 2);
+    assert!(started.elapsed() < Duration::from_millis(40));
+
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let crashed = block_on(spawn_blocking(|| -> usize { panic!("disk unplugged") }));
+    panic::set_hook(default_hook);
+    assert!(matches!(
+        crashed,
+        Err(FutError::Panicked(msg)) if msg == "disk unplugged"
+    ));
+
+    Ok(())
+}
+
+pub fn test_task_priorities() -> Result<(), FutError> {
+    let trace = Rc::new(RefCell::new(Vec::new()));
+    let traced = |name: &'static str, polls: usize| {
+        let trace = Rc::clone(&trace);
+        let mut remaining = polls;
+        poll_fn(move || {
+            trace.borrow_mut().push(name);
+            remaining -= 1;
+            if remaining == 0 {
+                return Ok::<_, FutError>(FutResult::finished(polls));
+            }
+            Ok(FutResult::pending())
+        })
+    };
+
+    let mut runner = PollRunner::new();
+    let bulk = runner.schedule_with_priority(traced("low", 1), Priority::Low);
+    let normal = runner.schedule(traced("normal", 1));
+    let urgent = runner.schedule_with_priority(traced("high", 20), Priority::High);
+    runner.run()?;
+
+    assert_eq!(urgent.try_result()?, 20);
+    assert_eq!(normal.try_result()?, 1);
+    assert_eq!(bulk.try_result()?, 1);
+
+    let trace = trace.borrow();
+    let position = |name| trace.iter().position(|polled| *polled == name).unwrap();
+    assert_eq!(trace[0], "high");
+    assert!(position("normal") < position("low"));
+    assert!(position("low") < trace.len() - 1);
+    assert_eq!(trace.last(), Some(&"high"));
+    assert!(Priority::High < Priority::Normal && Priority::Normal < Priority::Low);
+    assert_eq!(Priority::default(), Priority::Normal);
+
+    Ok(())
+}
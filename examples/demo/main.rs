@@ -0,0 +1,371 @@
+use fut_test::{
+    test_abortable, test_and_then, test_biased_select, test_cancellation_token, test_catch,
+    test_catch_unwind, test_chained_futures, test_circuit_breaker, test_debounce, test_delay,
+    test_either, test_err_into, test_error_kinds, test_find_tasks, test_from_callback, test_fuse,
+    test_future_cache, test_future_ext, test_futures_ordered, test_generic_outputs,
+    test_idle_busy_callbacks, test_inspect_err, test_join, test_join3_join4, test_join_all_limited,
+    test_join_all_settled, test_join_handle, test_lazy, test_loop_fn, test_map, test_map_err,
+    test_map_into, test_maybe_done, test_never, test_now_or_never, test_or_else, test_poll_fn,
+    test_poll_runner, test_polled_after_completion, test_race, test_rate_limiter,
+    test_ready_and_pending, test_remote_handle, test_reserve, test_retry, test_runner_extensions,
+    test_select, test_select_all, test_select_ok, test_sequential_execution, test_shared,
+    test_shutdown_signal, test_simple_runner, test_single_flight, test_size_report,
+    test_spawn_blocking, test_spawn_from_poll, test_start_shutdown_hooks, test_strict_mode,
+    test_take_result, test_task_priorities, test_timeout, test_try_flatten, test_try_join,
+    test_waker, test_weak_shared, test_yield_now, test_zip,
+};
+use futures::init_logging;
+use log::{debug, error, info};
+
+mod fut_test;
+
+fn main() {
+    init_logging();
+    run_tests();
+}
+
+fn run_tests() {
+    info!("Application started");
+
+    debug!("=== Testing Simple Runner ===\n");
+    if let Err(e) = test_simple_runner() {
+        error!("Simple runner test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Poll Runner ===\n");
+    if let Err(e) = test_poll_runner() {
+        error!("Poll runner test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Sequential Execution ===\n");
+    if let Err(e) = test_sequential_execution() {
+        error!("Sequential execution test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Chained Futures ===\n");
+    if let Err(e) = test_chained_futures() {
+        error!("Chained futures test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Polled After Completion ===\n");
+    if let Err(e) = test_polled_after_completion() {
+        error!("Polled after completion test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Take Result ===\n");
+    if let Err(e) = test_take_result() {
+        error!("Take result test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Strict Mode ===\n");
+    if let Err(e) = test_strict_mode() {
+        error!("Strict mode test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Map ===\n");
+    if let Err(e) = test_map() {
+        error!("Map test failed: {:?}", e);
+    }
+
+    debug!("=== Testing MapErr ===\n");
+    if let Err(e) = test_map_err() {
+        error!("MapErr test failed: {:?}", e);
+    }
+
+    debug!("=== Testing AndThen ===\n");
+    if let Err(e) = test_and_then() {
+        error!("AndThen test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Idle/Busy Callbacks ===\n");
+    if let Err(e) = test_idle_busy_callbacks() {
+        error!("Idle/busy callbacks test failed: {:?}", e);
+    }
+
+    debug!("=== Testing OrElse ===\n");
+    if let Err(e) = test_or_else() {
+        error!("OrElse test failed: {:?}", e);
+    }
+
+    debug!("=== Testing JoinAllSettled ===\n");
+    if let Err(e) = test_join_all_settled() {
+        error!("JoinAllSettled test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Join ===\n");
+    if let Err(e) = test_join() {
+        error!("Join test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Join3/Join4 ===\n");
+    if let Err(e) = test_join3_join4() {
+        error!("Join3/Join4 test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Reserve ===\n");
+    if let Err(e) = test_reserve() {
+        error!("Reserve test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Start/Shutdown Hooks ===\n");
+    if let Err(e) = test_start_shutdown_hooks() {
+        error!("Start/Shutdown Hooks test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Select ===\n");
+    if let Err(e) = test_select() {
+        error!("Select test failed: {:?}", e);
+    }
+
+    debug!("=== Testing SelectAll ===\n");
+    if let Err(e) = test_select_all() {
+        error!("SelectAll test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Race ===\n");
+    if let Err(e) = test_race() {
+        error!("Race test failed: {:?}", e);
+    }
+
+    debug!("=== Testing TryJoin ===\n");
+    if let Err(e) = test_try_join() {
+        error!("TryJoin test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Spawn From Poll ===\n");
+    if let Err(e) = test_spawn_from_poll() {
+        error!("Spawn From Poll test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Fuse ===\n");
+    if let Err(e) = test_fuse() {
+        error!("Fuse test failed: {:?}", e);
+    }
+
+    debug!("=== Testing InspectErr ===\n");
+    if let Err(e) = test_inspect_err() {
+        error!("InspectErr test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Catch ===\n");
+    if let Err(e) = test_catch() {
+        error!("Catch test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Either ===\n");
+    if let Err(e) = test_either() {
+        error!("Either test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Lazy ===\n");
+    if let Err(e) = test_lazy() {
+        error!("Lazy test failed: {:?}", e);
+    }
+
+    debug!("=== Testing PollFn ===\n");
+    if let Err(e) = test_poll_fn() {
+        error!("PollFn test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Find Tasks ===\n");
+    if let Err(e) = test_find_tasks() {
+        error!("Find Tasks test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Ready/Pending ===\n");
+    if let Err(e) = test_ready_and_pending() {
+        error!("Ready/Pending test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Error Kinds ===\n");
+    if let Err(e) = test_error_kinds() {
+        error!("Error Kinds test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Shared ===\n");
+    if let Err(e) = test_shared() {
+        error!("Shared test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Timeout ===\n");
+    if let Err(e) = test_timeout() {
+        error!("Timeout test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Delay ===\n");
+    if let Err(e) = test_delay() {
+        error!("Delay test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Retry ===\n");
+    if let Err(e) = test_retry() {
+        error!("Retry test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Shutdown Signal ===\n");
+    if let Err(e) = test_shutdown_signal() {
+        error!("Shutdown Signal test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Yield Now ===\n");
+    if let Err(e) = test_yield_now() {
+        error!("Yield Now test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Loop Fn ===\n");
+    if let Err(e) = test_loop_fn() {
+        error!("Loop Fn test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Future Ext ===\n");
+    if let Err(e) = test_future_ext() {
+        error!("Future Ext test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Zip ===\n");
+    if let Err(e) = test_zip() {
+        error!("Zip test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Single Flight ===\n");
+    if let Err(e) = test_single_flight() {
+        error!("Single Flight test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Err Into ===\n");
+    if let Err(e) = test_err_into() {
+        error!("Err Into test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Never ===\n");
+    if let Err(e) = test_never() {
+        error!("Never test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Abortable ===\n");
+    if let Err(e) = test_abortable() {
+        error!("Abortable test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Cancellation Token ===\n");
+    if let Err(e) = test_cancellation_token() {
+        error!("Cancellation Token test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Runner Extensions ===\n");
+    if let Err(e) = test_runner_extensions() {
+        error!("Runner Extensions test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Map Into ===\n");
+    if let Err(e) = test_map_into() {
+        error!("Map Into test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Try Flatten ===\n");
+    if let Err(e) = test_try_flatten() {
+        error!("Try Flatten test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Biased Select ===\n");
+    if let Err(e) = test_biased_select() {
+        error!("Biased Select test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Select Ok ===\n");
+    if let Err(e) = test_select_ok() {
+        error!("Select Ok test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Size Report ===\n");
+    if let Err(e) = test_size_report() {
+        error!("Size Report test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Join All Limited ===\n");
+    if let Err(e) = test_join_all_limited() {
+        error!("Join All Limited test failed: {:?}", e);
+    }
+
+    debug!("=== Testing From Callback ===\n");
+    if let Err(e) = test_from_callback() {
+        error!("From Callback test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Futures Ordered ===\n");
+    if let Err(e) = test_futures_ordered() {
+        error!("Futures Ordered test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Now Or Never ===\n");
+    if let Err(e) = test_now_or_never() {
+        error!("Now Or Never test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Maybe Done ===\n");
+    if let Err(e) = test_maybe_done() {
+        error!("Maybe Done test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Remote Handle ===\n");
+    if let Err(e) = test_remote_handle() {
+        error!("Remote Handle test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Catch Unwind ===\n");
+    if let Err(e) = test_catch_unwind() {
+        error!("Catch Unwind test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Weak Shared ===\n");
+    if let Err(e) = test_weak_shared() {
+        error!("Weak Shared test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Circuit Breaker ===\n");
+    if let Err(e) = test_circuit_breaker() {
+        error!("Circuit Breaker test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Future Cache ===\n");
+    if let Err(e) = test_future_cache() {
+        error!("Future Cache test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Rate Limiter ===\n");
+    if let Err(e) = test_rate_limiter() {
+        error!("Rate Limiter test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Debounce ===\n");
+    if let Err(e) = test_debounce() {
+        error!("Debounce test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Waker ===\n");
+    if let Err(e) = test_waker() {
+        error!("Waker test failed: {:?}", e);
+    }
+
+    debug!("=== Testing JoinHandle ===\n");
+    if let Err(e) = test_join_handle() {
+        error!("JoinHandle test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Generic Outputs ===\n");
+    if let Err(e) = test_generic_outputs() {
+        error!("Generic Outputs test failed: {:?}", e);
+    }
+
+    debug!("=== Testing spawn_blocking ===\n");
+    if let Err(e) = test_spawn_blocking() {
+        error!("spawn_blocking test failed: {:?}", e);
+    }
+
+    debug!("=== Testing Task Priorities ===\n");
+    if let Err(e) = test_task_priorities() {
+        error!("Task Priorities test failed: {:?}", e);
+    }
+
+    info!("All tests completed");
+}
@@ -0,0 +1,68 @@
+use proc_macro::{Ident, Span, TokenStream, TokenTree};
+
+#[proc_macro_attribute]
+pub fn main(args: TokenStream, item: TokenStream) -> TokenStream {
+    expand(args, item, |name, inner| {
+        format!(
+            "fn {name}() -> ::std::result::Result<(), ::futures::FutError> {{
+                ::futures::init_logging();
+                ::futures::block_on({inner}()).map(|_| ())
+            }}"
+        )
+    })
+}
+
+#[proc_macro_attribute]
+pub fn test(args: TokenStream, item: TokenStream) -> TokenStream {
+    expand(args, item, |name, inner| {
+        format!(
+            "#[test]
+            fn {name}() {{
+                ::futures::init_logging();
+                if let ::std::result::Result::Err(e) = ::futures::block_on({inner}()) {{
+                    ::std::panic!(\"{name} failed: {{:?}}\", e);
+                }}
+            }}"
+        )
+    })
+}
+
+fn expand(
+    args: TokenStream,
+    item: TokenStream,
+    wrapper: impl FnOnce(&str, &str) -> String,
+) -> TokenStream {
+    if !args.is_empty() {
+        return compile_error("this attribute takes no arguments");
+    }
+
+    let mut tokens: Vec<TokenTree> = item.into_iter().collect();
+    let name_pos = tokens
+        .iter()
+        .position(|token| matches!(token, TokenTree::Ident(ident) if ident.to_string() == "fn"))
+        .map(|pos| pos + 1);
+
+    let Some(TokenTree::Ident(name)) = name_pos.and_then(|pos| tokens.get(pos)).cloned() else {
+        return compile_error("expected a function returning a future");
+    };
+
+    let name = name.to_string();
+    let inner = format!("__futures_{}", name);
+    if let Some(pos) = name_pos {
+        tokens[pos] = TokenTree::Ident(Ident::new(&inner, Span::call_site()));
+    }
+
+    let mut output: TokenStream = tokens.into_iter().collect();
+    output.extend(
+        wrapper(&name, &inner)
+            .parse::<TokenStream>()
+            .expect("generated wrapper should parse"),
+    );
+    output
+}
+
+fn compile_error(message: &str) -> TokenStream {
+    format!("::std::compile_error!({:?});", message)
+        .parse()
+        .expect("compile_error! invocation should parse")
+}
@@ -0,0 +1,111 @@
+use crate::futures::runtime::Waker;
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+#[derive(Debug, Default)]
+struct AbortState {
+    aborted: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AbortHandle {
+    state: Arc<AbortState>,
+}
+
+impl AbortHandle {
+    pub fn abort(&self) {
+        debug!("Abort requested");
+        self.state.aborted.store(true, Ordering::Release);
+        let waker = self
+            .state
+            .waker
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.state.aborted.load(Ordering::Acquire)
+    }
+
+    fn register(&self, waker: Waker) {
+        *self
+            .state
+            .waker
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(waker);
+    }
+}
+
+#[derive(Debug)]
+pub struct Abortable<F> {
+    future: Option<F>,
+    handle: AbortHandle,
+}
+
+pub fn abortable<F: Future>(future: F) -> (Abortable<F>, AbortHandle) {
+    debug!("Creating new Abortable future");
+    let handle = AbortHandle {
+        state: Arc::default(),
+    };
+
+    let abortable = Abortable {
+        future: Some(future),
+        handle: handle.clone(),
+    };
+
+    (abortable, handle)
+}
+
+impl<F> Future for Abortable<F>
+where
+    F: Future,
+    F::Error: From<FutError>,
+{
+    type Output = F::Output;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Abortable future");
+        let Some(future) = self.future.as_mut() else {
+            error!("ERROR: Abortable future polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        };
+
+        if self.handle.is_aborted() {
+            debug!("Abortable future was aborted, discarding the inner future");
+            self.cleanup();
+            return Err(FutError::Aborted.into());
+        }
+
+        let res = future.poll();
+        if res.as_ref().map_or(true, |res| res.state == FutState::Done) {
+            self.cleanup();
+            return res;
+        }
+
+        if let Some(waker) = Waker::current() {
+            self.handle.register(waker);
+            if self.handle.is_aborted() {
+                debug!("Abortable future was aborted while polling, discarding the inner future");
+                self.cleanup();
+                return Err(FutError::Aborted.into());
+            }
+        }
+
+        res
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Abortable future");
+        if let Some(mut future) = self.future.take() {
+            future.cleanup();
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::{fmt::Debug, mem};
+
+#[derive(Debug, Clone)]
+enum AndThenState<F1, F2, Fn> {
+    First { future: F1, transform: Fn },
+    Second(F2),
+    Done,
+}
+
+#[derive(Debug, Clone)]
+pub struct AndThen<F1, F2, Fn> {
+    state: AndThenState<F1, F2, Fn>,
+}
+
+impl<F1, F2, Fn> AndThen<F1, F2, Fn>
+where
+    F1: Future,
+    F2: Future<Error = F1::Error>,
+    Fn: FnOnce(F1::Output) -> F2,
+{
+    pub fn new(future: F1, transform: Fn) -> Self {
+        debug!("Creating new AndThen future");
+        Self {
+            state: AndThenState::First { future, transform },
+        }
+    }
+}
+
+impl<F1, F2, Fn> Future for AndThen<F1, F2, Fn>
+where
+    F1: Future,
+    F2: Future<Error = F1::Error>,
+    F1::Error: Debug + From<FutError>,
+    F1::Output: Debug,
+    Fn: FnOnce(F1::Output) -> F2,
+{
+    type Output = F2::Output;
+    type Error = F1::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling AndThen future");
+        match mem::replace(&mut self.state, AndThenState::Done) {
+            AndThenState::First {
+                mut future,
+                transform,
+            } => match future.poll() {
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: Some(value),
+                }) => {
+                    debug!("AndThen first future completed with value {:?}", value);
+                    future.cleanup();
+                    self.state = AndThenState::Second(transform(value));
+                    Ok(FutResult {
+                        state: FutState::Pending,
+                        value: None,
+                    })
+                }
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: None,
+                }) => {
+                    error!("ERROR: AndThen first future completed without value!");
+                    future.cleanup();
+                    Err(FutError::CompletedWithoutValue.into())
+                }
+                Ok(FutResult { state, .. }) => {
+                    self.state = AndThenState::First { future, transform };
+                    Ok(FutResult { state, value: None })
+                }
+                Err(e) => {
+                    error!("AndThen first future failed, skipping transform: {:?}", e);
+                    future.cleanup();
+                    Err(e)
+                }
+            },
+            AndThenState::Second(mut future) => match future.poll() {
+                Ok(res) if res.state == FutState::Done => {
+                    future.cleanup();
+                    Ok(res)
+                }
+                Ok(res) => {
+                    self.state = AndThenState::Second(future);
+                    Ok(res)
+                }
+                Err(e) => {
+                    error!("AndThen second future poll resulted in error {:?}", e);
+                    future.cleanup();
+                    Err(e)
+                }
+            },
+            AndThenState::Done => {
+                error!("ERROR: AndThen future polled after completion!");
+                Err(FutError::PolledAfterCompletion.into())
+            }
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying AndThen future");
+        match self.state {
+            AndThenState::First { ref mut future, .. } => future.cleanup(),
+            AndThenState::Second(ref mut future) => future.cleanup(),
+            AndThenState::Done => {}
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::futures::catch_unwind::panic_message;
+use crate::futures::{from_callback, CallbackHandle, FutError, FutResult, Future};
+use log::{debug, error};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::thread;
+
+const BLOCKING_THREADS: usize = 4;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct BlockingPool {
+    jobs: Mutex<Sender<Job>>,
+}
+
+impl BlockingPool {
+    fn global() -> &'static BlockingPool {
+        static POOL: OnceLock<BlockingPool> = OnceLock::new();
+        POOL.get_or_init(|| Self::new(BLOCKING_THREADS))
+    }
+
+    fn new(threads: usize) -> Self {
+        debug!("Starting blocking pool with {} threads", threads);
+        let (jobs, queue) = mpsc::channel::<Job>();
+        let queue = Arc::new(Mutex::new(queue));
+        for worker in 0..threads {
+            let queue = Arc::clone(&queue);
+            thread::Builder::new()
+                .name(format!("blocking-{}", worker))
+                .spawn(move || Self::work(&queue))
+                .expect("failed to start blocking pool thread");
+        }
+
+        Self {
+            jobs: Mutex::new(jobs),
+        }
+    }
+
+    fn work(queue: &Mutex<Receiver<Job>>) {
+        loop {
+            let job = queue.lock().unwrap_or_else(PoisonError::into_inner).recv();
+            let Ok(job) = job else {
+                return;
+            };
+
+            if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+                error!("ERROR: Blocking job panicked");
+            }
+        }
+    }
+
+    fn execute(&self, job: Job) {
+        let sent = self
+            .jobs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .send(job);
+        if sent.is_err() {
+            error!("ERROR: Blocking pool is shut down, dropping job");
+        }
+    }
+}
+
+struct SpawnBlocking<F> {
+    inner: F,
+}
+
+impl<F, T> Future for SpawnBlocking<F>
+where
+    F: Future<Output = Result<T, FutError>, Error = FutError>,
+{
+    type Output = T;
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling SpawnBlocking future");
+        let FutResult { state, value } = self.inner.poll()?;
+        match value {
+            Some(Ok(value)) => Ok(FutResult {
+                state,
+                value: Some(value),
+            }),
+            Some(Err(err)) => {
+                error!("SpawnBlocking job failed with {:?}", err);
+                Err(err)
+            }
+            None => Ok(FutResult { state, value: None }),
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying SpawnBlocking future");
+        self.inner.cleanup();
+    }
+}
+
+pub fn spawn_blocking<F, T>(work: F) -> impl Future<Output = T, Error = FutError>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    debug!("Creating new SpawnBlocking future");
+    let inner = from_callback(move |handle: CallbackHandle<Result<T, FutError>>| {
+        BlockingPool::global().execute(Box::new(move || {
+            let res = panic::catch_unwind(AssertUnwindSafe(work))
+                .map_err(|payload| FutError::Panicked(panic_message(payload)));
+            handle.complete(res);
+        }));
+    });
+    SpawnBlocking { inner }
+}
@@ -0,0 +1,130 @@
+use crate::futures::runtime::Waker;
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+
+#[derive(Debug, Default)]
+struct TokenState {
+    cancelled: AtomicBool,
+    parent: Option<Arc<TokenState>>,
+    children: Mutex<Vec<Weak<TokenState>>>,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl TokenState {
+    fn register(&self, waker: Waker) {
+        let mut wakers = self.wakers.lock().unwrap_or_else(PoisonError::into_inner);
+        if !wakers.iter().any(|registered| registered.will_wake(&waker)) {
+            wakers.push(waker);
+        }
+    }
+
+    fn wake_all(&self) {
+        let wakers = mem::take(&mut *self.wakers.lock().unwrap_or_else(PoisonError::into_inner));
+        for waker in wakers {
+            waker.wake();
+        }
+
+        let children = self.children.lock().unwrap_or_else(PoisonError::into_inner);
+        for child in children.iter().filter_map(Weak::upgrade) {
+            child.wake_all();
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+            || self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.is_cancelled())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<TokenState>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        debug!("Creating new CancellationToken");
+        Default::default()
+    }
+
+    pub fn child_token(&self) -> Self {
+        debug!("Creating child CancellationToken");
+        let state = Arc::new(TokenState {
+            cancelled: AtomicBool::new(false),
+            parent: Some(Arc::clone(&self.state)),
+            ..Default::default()
+        });
+        let mut children = self
+            .state
+            .children
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        children.retain(|child| child.strong_count() > 0);
+        children.push(Arc::downgrade(&state));
+        drop(children);
+        Self { state }
+    }
+
+    pub fn cancel(&self) {
+        debug!("Cancelling CancellationToken");
+        self.state.cancelled.store(true, Ordering::Release);
+        self.state.wake_all();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.is_cancelled()
+    }
+
+    pub fn cancelled(&self) -> Cancelled {
+        Cancelled {
+            token: self.clone(),
+            finished: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Cancelled {
+    token: CancellationToken,
+    finished: bool,
+}
+
+impl Future for Cancelled {
+    type Output = ();
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Cancelled future");
+        if self.finished {
+            error!("ERROR: Cancelled future polled after completion!");
+            return Err(FutError::PolledAfterCompletion);
+        }
+
+        if !self.token.is_cancelled() {
+            let Some(waker) = Waker::current() else {
+                return Ok(FutResult::pending());
+            };
+
+            self.token.state.register(waker);
+            if !self.token.is_cancelled() {
+                return Ok(FutResult {
+                    state: FutState::Waiting,
+                    value: None,
+                });
+            }
+        }
+
+        self.finished = true;
+        Ok(FutResult::finished(()))
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Cancelled future");
+    }
+}
@@ -0,0 +1,66 @@
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::fmt::Debug;
+
+#[derive(Debug, Clone)]
+pub struct Catch<F, Fn> {
+    future: Option<F>,
+    recover: Option<Fn>,
+}
+
+impl<F, Fn> Catch<F, Fn>
+where
+    F: Future,
+    Fn: FnOnce(F::Error) -> F::Output,
+{
+    pub fn new(future: F, recover: Fn) -> Self {
+        debug!("Creating new Catch future");
+        Self {
+            future: Some(future),
+            recover: Some(recover),
+        }
+    }
+}
+
+impl<F, Fn> Future for Catch<F, Fn>
+where
+    F: Future,
+    F::Output: Debug,
+    F::Error: Debug,
+    Fn: FnOnce(F::Error) -> F::Output,
+{
+    type Output = F::Output;
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Catch future");
+        let future = self.future.as_mut().ok_or_else(|| {
+            error!("ERROR: Catch future polled after completion!");
+            FutError::PolledAfterCompletion
+        })?;
+
+        match future.poll() {
+            Ok(res) => {
+                if res.state == FutState::Done {
+                    future.cleanup();
+                    self.future = None;
+                }
+                Ok(res)
+            }
+            Err(e) => {
+                debug!("Catch recovering from error {:?}", e);
+                future.cleanup();
+                self.future = None;
+                let recover = self.recover.take().ok_or(FutError::PolledAfterCompletion)?;
+                Ok(FutResult::finished(recover(e)))
+            }
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Catch future");
+        if let Some(future) = self.future.as_mut() {
+            future.cleanup();
+        }
+    }
+}
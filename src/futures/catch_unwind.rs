@@ -0,0 +1,61 @@
+use crate::futures::{FutError, FutResult, Future};
+use log::{debug, error};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+#[derive(Debug)]
+pub struct CatchUnwind<F> {
+    future: Option<F>,
+}
+
+impl<F: Future> CatchUnwind<F> {
+    pub fn new(future: F) -> Self {
+        debug!("Creating new CatchUnwind future");
+        Self {
+            future: Some(future),
+        }
+    }
+}
+
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map_or_else(|| "non-string panic payload".to_string(), |s| s.to_string()),
+    }
+}
+
+impl<F> Future for CatchUnwind<F>
+where
+    F: Future,
+    F::Error: From<FutError>,
+{
+    type Output = F::Output;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling CatchUnwind future");
+        let Some(future) = self.future.as_mut() else {
+            error!("ERROR: CatchUnwind future polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        };
+
+        match panic::catch_unwind(AssertUnwindSafe(|| future.poll())) {
+            Ok(res) => res,
+            Err(payload) => {
+                let message = panic_message(payload);
+                error!("CatchUnwind inner future panicked: {}", message);
+                self.future = None;
+                Err(FutError::Panicked(message).into())
+            }
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying CatchUnwind future");
+        if let Some(future) = self.future.as_mut() {
+            future.cleanup();
+        }
+    }
+}
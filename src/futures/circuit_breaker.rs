@@ -0,0 +1,166 @@
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error, warn};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct Breaker {
+    threshold: usize,
+    cooldown: Duration,
+    failures: usize,
+    open_until: Option<Instant>,
+    probing: bool,
+}
+
+impl Breaker {
+    fn state(&self) -> CircuitState {
+        match self.open_until {
+            None => CircuitState::Closed,
+            Some(_) if self.probing => CircuitState::HalfOpen,
+            Some(until) if Instant::now() >= until => CircuitState::HalfOpen,
+            Some(_) => CircuitState::Open,
+        }
+    }
+
+    fn admit(&mut self) -> Result<bool, FutError> {
+        match self.state() {
+            CircuitState::Closed => Ok(false),
+            CircuitState::HalfOpen if !self.probing => {
+                debug!("Circuit half-open, admitting a probe");
+                self.probing = true;
+                Ok(true)
+            }
+            _ => Err(FutError::CircuitOpen),
+        }
+    }
+
+    fn record_success(&mut self) {
+        if self.open_until.is_some() {
+            debug!("Circuit probe succeeded, closing circuit");
+        }
+        self.failures = 0;
+        self.open_until = None;
+        self.probing = false;
+    }
+
+    fn record_failure(&mut self) {
+        self.failures += 1;
+        if self.probing || self.failures >= self.threshold {
+            warn!(
+                "Circuit opening for {:?} after {} consecutive failures",
+                self.cooldown, self.failures
+            );
+            self.open_until = Some(Instant::now() + self.cooldown);
+            self.probing = false;
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    breaker: Rc<RefCell<Breaker>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: usize, cooldown: Duration) -> Self {
+        debug!(
+            "Creating new CircuitBreaker with threshold {} and cooldown {:?}",
+            threshold, cooldown
+        );
+        Self {
+            breaker: Rc::new(RefCell::new(Breaker {
+                threshold: threshold.max(1),
+                cooldown,
+                failures: 0,
+                open_until: None,
+                probing: false,
+            })),
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.breaker.borrow().state()
+    }
+
+    pub fn call<F: Future>(&self, future: F) -> Guarded<F> {
+        Guarded {
+            future: Some(future),
+            breaker: Rc::clone(&self.breaker),
+            admitted: false,
+            probe: false,
+        }
+    }
+}
+
+pub struct Guarded<F> {
+    future: Option<F>,
+    breaker: Rc<RefCell<Breaker>>,
+    admitted: bool,
+    probe: bool,
+}
+
+impl<F> Future for Guarded<F>
+where
+    F: Future,
+    F::Error: From<FutError>,
+{
+    type Output = F::Output;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling CircuitBreaker guarded future");
+        let Some(future) = self.future.as_mut() else {
+            error!("ERROR: CircuitBreaker guarded future polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        };
+
+        if !self.admitted {
+            let admitted = self.breaker.borrow_mut().admit();
+            match admitted {
+                Ok(probe) => self.probe = probe,
+                Err(e) => {
+                    debug!("Circuit open, failing fast");
+                    self.cleanup();
+                    return Err(e.into());
+                }
+            }
+            self.admitted = true;
+        }
+
+        match future.poll() {
+            Ok(res) if res.state == FutState::Done => {
+                self.probe = false;
+                self.breaker.borrow_mut().record_success();
+                self.cleanup();
+                Ok(res)
+            }
+            Ok(res) => Ok(res),
+            Err(e) => {
+                self.probe = false;
+                self.breaker.borrow_mut().record_failure();
+                self.cleanup();
+                Err(e)
+            }
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying CircuitBreaker guarded future");
+        if let Some(mut future) = self.future.take() {
+            future.cleanup();
+            if self.probe {
+                debug!("Circuit probe abandoned, allowing another");
+                self.probe = false;
+                self.breaker.borrow_mut().probing = false;
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use crate::futures::runtime::wake_at;
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct DebounceHandle {
+    deadline: Rc<Cell<Instant>>,
+    quiet: Duration,
+}
+
+impl DebounceHandle {
+    pub fn trigger(&self) {
+        debug!("Resetting Debounce deadline");
+        self.deadline.set(Instant::now() + self.quiet);
+    }
+}
+
+pub struct Debounce<F: Future> {
+    future: Option<F>,
+    value: Option<F::Output>,
+    handle: DebounceHandle,
+    finished: bool,
+}
+
+impl<F: Future> Debounce<F> {
+    pub fn new(future: F, quiet: Duration) -> Self {
+        debug!("Creating new Debounce future with quiet window {:?}", quiet);
+        Self {
+            future: Some(future),
+            value: None,
+            handle: DebounceHandle {
+                deadline: Rc::new(Cell::new(Instant::now() + quiet)),
+                quiet,
+            },
+            finished: false,
+        }
+    }
+
+    pub fn handle(&self) -> DebounceHandle {
+        self.handle.clone()
+    }
+}
+
+impl<F> Future for Debounce<F>
+where
+    F: Future,
+    F::Error: From<FutError>,
+{
+    type Output = F::Output;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Debounce future");
+        if self.finished {
+            error!("ERROR: Debounce future polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        }
+
+        if let Some(future) = self.future.as_mut() {
+            let res = match future.poll() {
+                Ok(res) => res,
+                Err(e) => {
+                    self.cleanup();
+                    return Err(e);
+                }
+            };
+
+            if res.state != FutState::Done {
+                return Ok(res);
+            }
+
+            let value = res.value.ok_or(FutError::CompletedWithoutValue)?;
+            debug!("Debounce inner future completed, waiting for quiet window");
+            self.value = Some(value);
+            if let Some(mut future) = self.future.take() {
+                future.cleanup();
+            }
+        }
+
+        let deadline = self.handle.deadline.get();
+        if Instant::now() < deadline {
+            wake_at(deadline);
+            return Ok(FutResult {
+                state: FutState::Waiting,
+                value: None,
+            });
+        }
+
+        debug!("Debounce quiet window elapsed");
+        self.finished = true;
+        Ok(FutResult {
+            state: FutState::Done,
+            value: self.value.take(),
+        })
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Debounce future");
+        self.finished = true;
+        self.value = None;
+        if let Some(mut future) = self.future.take() {
+            future.cleanup();
+        }
+    }
+}
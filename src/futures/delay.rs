@@ -0,0 +1,48 @@
+use crate::futures::runtime::wake_at;
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Delay {
+    deadline: Instant,
+    finished: bool,
+}
+
+impl Delay {
+    pub fn new(duration: Duration) -> Self {
+        debug!("Creating new Delay future with duration {:?}", duration);
+        Self {
+            deadline: Instant::now() + duration,
+            finished: false,
+        }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Delay future");
+        if self.finished {
+            error!("ERROR: Delay future polled after completion!");
+            return Err(FutError::PolledAfterCompletion);
+        }
+
+        if Instant::now() >= self.deadline {
+            self.finished = true;
+            return Ok(FutResult::finished(()));
+        }
+
+        wake_at(self.deadline);
+        Ok(FutResult {
+            state: FutState::Waiting,
+            value: None,
+        })
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Delay future");
+    }
+}
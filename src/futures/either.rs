@@ -0,0 +1,38 @@
+use crate::futures::{FutResult, Future};
+use log::debug;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Either<A, B> {
+    Left(A),
+    Right(B),
+}
+
+impl<A, B> Future for Either<A, B>
+where
+    A: Future,
+    B: Future<Output = A::Output, Error = A::Error>,
+{
+    type Output = A::Output;
+    type Error = A::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        match self {
+            Either::Left(future) => {
+                debug!("Polling Either future on the left");
+                future.poll()
+            }
+            Either::Right(future) => {
+                debug!("Polling Either future on the right");
+                future.poll()
+            }
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Either future");
+        match self {
+            Either::Left(future) => future.cleanup(),
+            Either::Right(future) => future.cleanup(),
+        }
+    }
+}
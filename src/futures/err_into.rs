@@ -0,0 +1,47 @@
+use crate::futures::{FutResult, Future};
+use log::{debug, error};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct ErrInto<F, E> {
+    future: F,
+    _error: PhantomData<E>,
+}
+
+impl<F, E> ErrInto<F, E>
+where
+    F: Future,
+    E: From<F::Error>,
+{
+    pub fn new(future: F) -> Self {
+        debug!("Creating new ErrInto future");
+        Self {
+            future,
+            _error: PhantomData,
+        }
+    }
+}
+
+impl<F, E> Future for ErrInto<F, E>
+where
+    F: Future,
+    F::Error: Debug,
+    E: From<F::Error>,
+{
+    type Output = F::Output;
+    type Error = E;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling ErrInto future");
+        self.future.poll().map_err(|e| {
+            error!("ErrInto inner future poll resulted in error {:?}", e);
+            E::from(e)
+        })
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying ErrInto future");
+        self.future.cleanup();
+    }
+}
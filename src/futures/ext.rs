@@ -0,0 +1,165 @@
+use crate::futures::{
+    now_or_never, remote_handle, zip, AndThen, Catch, CatchUnwind, Chain, ErrInto, Fuse, Future,
+    Inspect, InspectErr, Join, Map, MapErr, MapInto, OrElse, Race, Remote, RemoteHandle, Select,
+    Timeout, TryFlatten, TryJoin, Zip,
+};
+use std::time::Duration;
+
+pub type BoxFuture<T, E> = Box<dyn Future<Output = T, Error = E>>;
+
+pub trait FutureExt: Future + Sized {
+    fn then<F, Fn>(self, transform: Fn) -> Chain<Self, F, Fn>
+    where
+        F: Future,
+        Fn: FnOnce(Self::Output) -> F,
+    {
+        Chain::new(self, transform)
+    }
+
+    fn then_map_err<F, Fn, M>(self, transform: Fn, err_map: M) -> Chain<Self, F, Fn, M>
+    where
+        F: Future,
+        Fn: FnOnce(Self::Output) -> F,
+        M: FnMut(F::Error) -> Self::Error,
+    {
+        Chain::new_with_err_map(self, transform, err_map)
+    }
+
+    fn map<Fn, T>(self, transform: Fn) -> Map<Self, Fn>
+    where
+        Fn: FnOnce(Self::Output) -> T,
+    {
+        Map::new(self, transform)
+    }
+
+    fn map_into<T>(self) -> MapInto<Self, T>
+    where
+        Self::Output: Into<T>,
+    {
+        MapInto::new(self)
+    }
+
+    fn map_err<Fn, E>(self, err_map: Fn) -> MapErr<Self, Fn>
+    where
+        Fn: FnMut(Self::Error) -> E,
+    {
+        MapErr::new(self, err_map)
+    }
+
+    fn err_into<E>(self) -> ErrInto<Self, E>
+    where
+        E: From<Self::Error>,
+    {
+        ErrInto::new(self)
+    }
+
+    fn and_then<F, Fn>(self, transform: Fn) -> AndThen<Self, F, Fn>
+    where
+        F: Future<Error = Self::Error>,
+        Fn: FnOnce(Self::Output) -> F,
+    {
+        AndThen::new(self, transform)
+    }
+
+    fn or_else<F, Fn>(self, fallback: Fn) -> OrElse<Self, F, Fn>
+    where
+        F: Future<Output = Self::Output>,
+        Fn: FnOnce(Self::Error) -> F,
+    {
+        OrElse::new(self, fallback)
+    }
+
+    fn catch<Fn>(self, recover: Fn) -> Catch<Self, Fn>
+    where
+        Fn: FnOnce(Self::Error) -> Self::Output,
+    {
+        Catch::new(self, recover)
+    }
+
+    fn catch_unwind(self) -> CatchUnwind<Self> {
+        CatchUnwind::new(self)
+    }
+
+    fn inspect<Fn>(self, inspect: Fn) -> Inspect<Self, Fn>
+    where
+        Fn: FnMut(&Self::Output),
+    {
+        Inspect::new(self, inspect)
+    }
+
+    fn inspect_err<Fn>(self, inspect: Fn) -> InspectErr<Self, Fn>
+    where
+        Fn: FnMut(&Self::Error),
+    {
+        InspectErr::new(self, inspect)
+    }
+
+    fn join<F>(self, other: F) -> Join<Self, F>
+    where
+        F: Future<Error = Self::Error>,
+    {
+        Join::new(self, other)
+    }
+
+    fn try_join<F>(self, other: F) -> TryJoin<Self, F>
+    where
+        F: Future<Error = Self::Error>,
+    {
+        TryJoin::new(self, other)
+    }
+
+    fn zip<F>(self, other: F) -> Zip<Self, F>
+    where
+        F: Future<Error = Self::Error>,
+    {
+        zip(self, other)
+    }
+
+    fn select<F>(self, other: F) -> Select<Self, F>
+    where
+        F: Future<Error = Self::Error>,
+    {
+        Select::new(self, other)
+    }
+
+    fn race<F>(self, other: F) -> Race<Self, F>
+    where
+        F: Future<Output = Self::Output, Error = Self::Error>,
+    {
+        Race::new(self, other)
+    }
+
+    fn try_flatten<F, E>(self) -> TryFlatten<Self, F>
+    where
+        Self: Future<Output = Result<F, E>>,
+        F: Future<Error = Self::Error>,
+        E: Into<Self::Error>,
+    {
+        TryFlatten::new(self)
+    }
+
+    fn fuse(self) -> Fuse<Self> {
+        Fuse::new(self)
+    }
+
+    fn timeout(self, duration: Duration) -> Timeout<Self> {
+        Timeout::new(self, duration)
+    }
+
+    fn now_or_never(self) -> Result<Option<Self::Output>, Self::Error> {
+        now_or_never(self)
+    }
+
+    fn remote_handle(self) -> (Remote<Self>, RemoteHandle<Self::Output, Self::Error>) {
+        remote_handle(self)
+    }
+
+    fn boxed(self) -> BoxFuture<Self::Output, Self::Error>
+    where
+        Self: 'static,
+    {
+        Box::new(self)
+    }
+}
+
+impl<F: Future> FutureExt for F {}
@@ -0,0 +1,119 @@
+use crate::futures::runtime::Waker;
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error, warn};
+use std::sync::{Arc, Mutex, PoisonError};
+
+#[derive(Debug)]
+struct Slot<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+#[derive(Debug)]
+pub struct CallbackHandle<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T> CallbackHandle<T> {
+    pub fn complete(self, value: T) {
+        debug!("Callback completed");
+        match self.slot.lock() {
+            Ok(mut slot) => slot.value = Some(value),
+            Err(_) => error!("ERROR: Callback slot poisoned, dropping value"),
+        }
+    }
+}
+
+impl<T> Drop for CallbackHandle<T> {
+    fn drop(&mut self) {
+        let mut slot = self.slot.lock().unwrap_or_else(PoisonError::into_inner);
+        slot.closed = true;
+        let waker = slot.waker.take();
+        drop(slot);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+pub struct FromCallback<T, R> {
+    register: Option<R>,
+    slot: Arc<Mutex<Slot<T>>>,
+    finished: bool,
+}
+
+pub fn from_callback<T, R>(register: R) -> FromCallback<T, R>
+where
+    R: FnOnce(CallbackHandle<T>),
+{
+    debug!("Creating new FromCallback future");
+    FromCallback {
+        register: Some(register),
+        slot: Arc::new(Mutex::new(Slot {
+            value: None,
+            waker: None,
+            closed: false,
+        })),
+        finished: false,
+    }
+}
+
+impl<T, R> Future for FromCallback<T, R>
+where
+    R: FnOnce(CallbackHandle<T>),
+{
+    type Output = T;
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling FromCallback future");
+        if self.finished {
+            error!("ERROR: FromCallback future polled after completion!");
+            return Err(FutError::PolledAfterCompletion);
+        }
+
+        if let Some(register) = self.register.take() {
+            debug!("Registering FromCallback completion handle");
+            register(CallbackHandle {
+                slot: Arc::clone(&self.slot),
+            });
+        }
+
+        let mut slot = self
+            .slot
+            .lock()
+            .map_err(|_| FutError::CompletedWithoutValue)?;
+        match slot.value.take() {
+            Some(value) => {
+                self.finished = true;
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: Some(value),
+                })
+            }
+            None if slot.closed => {
+                warn!("FromCallback handle dropped without completing");
+                self.finished = true;
+                Err(FutError::CompletedWithoutValue)
+            }
+            None => match Waker::current() {
+                Some(waker) => {
+                    slot.waker = Some(waker);
+                    Ok(FutResult {
+                        state: FutState::Waiting,
+                        value: None,
+                    })
+                }
+                None => Ok(FutResult {
+                    state: FutState::Pending,
+                    value: None,
+                }),
+            },
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying FromCallback future");
+    }
+}
@@ -0,0 +1,58 @@
+use crate::futures::{FutResult, FutState, Future};
+use log::debug;
+
+#[derive(Debug, Clone)]
+pub struct Fuse<F> {
+    future: Option<F>,
+}
+
+impl<F: Future> Fuse<F> {
+    pub fn new(future: F) -> Self {
+        debug!("Creating new Fuse future");
+        Self {
+            future: Some(future),
+        }
+    }
+
+    pub fn is_terminated(&self) -> bool {
+        self.future.is_none()
+    }
+}
+
+impl<F: Future> Future for Fuse<F> {
+    type Output = F::Output;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Fuse future");
+        let Some(future) = self.future.as_mut() else {
+            debug!("Fuse future already terminated, staying pending");
+            return Ok(FutResult {
+                state: FutState::Pending,
+                value: None,
+            });
+        };
+
+        let result = future.poll();
+        if !matches!(
+            result,
+            Ok(FutResult {
+                state: FutState::Pending | FutState::Waiting,
+                ..
+            })
+        ) {
+            debug!("Fuse inner future terminated");
+            future.cleanup();
+            self.future = None;
+        }
+
+        result
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Fuse future");
+        if let Some(future) = self.future.as_mut() {
+            future.cleanup();
+        }
+    }
+}
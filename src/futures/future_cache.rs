@@ -0,0 +1,84 @@
+use crate::futures::{BoxFuture, FutError, Future, Shared};
+use log::debug;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+type Entry<T> = Shared<BoxFuture<T, FutError>>;
+
+pub struct FutureCache<K, T> {
+    entries: HashMap<K, Entry<T>>,
+    recency: VecDeque<K>,
+    capacity: usize,
+}
+
+impl<K, T> FutureCache<K, T>
+where
+    K: Eq + Hash + Clone + Debug,
+    T: 'static,
+{
+    pub fn new(capacity: usize) -> Self {
+        debug!("Creating new FutureCache with capacity {}", capacity);
+        Self {
+            entries: HashMap::new(),
+            recency: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn get_or_insert<F, Fn>(&mut self, key: K, factory: Fn) -> Entry<T>
+    where
+        F: Future<Output = T, Error = FutError> + 'static,
+        Fn: FnOnce() -> F,
+    {
+        if self.entries.get(&key).is_some_and(Shared::is_failed) {
+            debug!("FutureCache dropping failed entry for {:?}", key);
+            self.remove(&key);
+        }
+
+        if let Some(entry) = self.entries.get(&key) {
+            debug!("FutureCache hit for {:?}", key);
+            let entry = entry.clone();
+            self.touch(&key);
+            return entry;
+        }
+
+        debug!("FutureCache miss for {:?}", key);
+        let entry = Shared::new(Box::new(factory()) as BoxFuture<T, FutError>);
+        self.entries.insert(key.clone(), entry.clone());
+        self.recency.push_back(key);
+        self.evict();
+        entry
+    }
+
+    pub fn remove(&mut self, key: &K) -> bool {
+        self.recency.retain(|k| k != key);
+        self.entries.remove(key).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn touch(&mut self, key: &K) {
+        if let Some(pos) = self.recency.iter().position(|k| k == key) {
+            if let Some(key) = self.recency.remove(pos) {
+                self.recency.push_back(key);
+            }
+        }
+    }
+
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            let Some(key) = self.recency.pop_front() else {
+                break;
+            };
+            debug!("FutureCache evicting least recently used {:?}", key);
+            self.entries.remove(&key);
+        }
+    }
+}
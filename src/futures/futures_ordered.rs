@@ -0,0 +1,132 @@
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+pub struct FuturesOrdered<F: Future> {
+    in_progress: Vec<(usize, F)>,
+    buffered: BTreeMap<usize, Option<F::Output>>,
+    next_in: usize,
+    next_out: usize,
+}
+
+impl<F: Future> FuturesOrdered<F> {
+    pub fn new() -> Self {
+        debug!("Creating new FuturesOrdered collection");
+        Self {
+            in_progress: Vec::new(),
+            buffered: BTreeMap::new(),
+            next_in: 0,
+            next_out: 0,
+        }
+    }
+
+    pub fn push_back(&mut self, future: F) {
+        debug!("FuturesOrdered queued future {}", self.next_in);
+        self.in_progress.push((self.next_in, future));
+        self.next_in += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.in_progress.len() + self.buffered.values().filter(|v| v.is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<F: Future> Default for FuturesOrdered<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Future> FromIterator<F> for FuturesOrdered<F> {
+    fn from_iter<I: IntoIterator<Item = F>>(futures: I) -> Self {
+        let mut ordered = Self::new();
+        for future in futures {
+            ordered.push_back(future);
+        }
+        ordered
+    }
+}
+
+impl<F> FuturesOrdered<F>
+where
+    F: Future,
+    F::Output: Debug,
+    F::Error: Debug + From<FutError>,
+{
+    pub fn poll_next(&mut self) -> Result<FutResult<Option<F::Output>>, F::Error> {
+        debug!("Polling FuturesOrdered collection");
+        let mut state = FutState::Done;
+        let mut slot = 0;
+        while slot < self.in_progress.len() {
+            let (seq, future) = &mut self.in_progress[slot];
+            let seq = *seq;
+            match future.poll() {
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: Some(value),
+                }) => {
+                    debug!("FuturesOrdered future {} completed with {:?}", seq, value);
+                    self.in_progress.swap_remove(slot).1.cleanup();
+                    self.buffered.insert(seq, Some(value));
+                }
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: None,
+                }) => {
+                    error!(
+                        "ERROR: FuturesOrdered future {} completed without value!",
+                        seq
+                    );
+                    self.in_progress.swap_remove(slot).1.cleanup();
+                    self.buffered.insert(seq, None);
+                    return Err(FutError::CompletedWithoutValue.into());
+                }
+                Ok(FutResult {
+                    state: child_state, ..
+                }) => {
+                    state = state.combine(child_state);
+                    slot += 1;
+                }
+                Err(e) => {
+                    error!("FuturesOrdered future {} failed with {:?}", seq, e);
+                    self.in_progress.swap_remove(slot).1.cleanup();
+                    self.buffered.insert(seq, None);
+                    return Err(e);
+                }
+            }
+        }
+
+        while let Some(output) = self.buffered.remove(&self.next_out) {
+            self.next_out += 1;
+            if let Some(value) = output {
+                return Ok(FutResult {
+                    state: FutState::Done,
+                    value: Some(Some(value)),
+                });
+            }
+        }
+
+        if self.is_empty() {
+            debug!("FuturesOrdered collection exhausted");
+            return Ok(FutResult {
+                state: FutState::Done,
+                value: Some(None),
+            });
+        }
+
+        Ok(FutResult { state, value: None })
+    }
+
+    pub fn cleanup(&mut self) {
+        debug!("Destroying FuturesOrdered collection");
+        for (_, future) in self.in_progress.iter_mut() {
+            future.cleanup();
+        }
+        self.in_progress.clear();
+    }
+}
@@ -0,0 +1,79 @@
+use crate::futures::{FutResult, Future};
+use log::debug;
+
+#[derive(Debug, Clone)]
+pub struct Inspect<F, Fn> {
+    future: F,
+    inspect: Fn,
+}
+
+impl<F, Fn> Inspect<F, Fn>
+where
+    F: Future,
+    Fn: FnMut(&F::Output),
+{
+    pub fn new(future: F, inspect: Fn) -> Self {
+        debug!("Creating new Inspect future");
+        Self { future, inspect }
+    }
+}
+
+impl<F, Fn> Future for Inspect<F, Fn>
+where
+    F: Future,
+    Fn: FnMut(&F::Output),
+{
+    type Output = F::Output;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Inspect future");
+        let res = self.future.poll()?;
+        if let Some(value) = res.value.as_ref() {
+            (self.inspect)(value);
+        }
+
+        Ok(res)
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Inspect future");
+        self.future.cleanup();
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InspectErr<F, Fn> {
+    future: F,
+    inspect: Fn,
+}
+
+impl<F, Fn> InspectErr<F, Fn>
+where
+    F: Future,
+    Fn: FnMut(&F::Error),
+{
+    pub fn new(future: F, inspect: Fn) -> Self {
+        debug!("Creating new InspectErr future");
+        Self { future, inspect }
+    }
+}
+
+impl<F, Fn> Future for InspectErr<F, Fn>
+where
+    F: Future,
+    Fn: FnMut(&F::Error),
+{
+    type Output = F::Output;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling InspectErr future");
+        self.future.poll().inspect_err(|e| (self.inspect)(e))
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying InspectErr future");
+        self.future.cleanup();
+    }
+}
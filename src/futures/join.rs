@@ -0,0 +1,125 @@
+use crate::futures::{FutError, FutResult, FutState, Future, MaybeDone};
+use log::{debug, error};
+use std::fmt::Debug;
+
+macro_rules! join_future {
+    ($name:ident, $try_name:ident; $F1:ident $f1:ident $i1:tt $(, $F:ident $f:ident $i:tt)+) => {
+        pub struct $name<$F1: Future, $($F: Future),+> {
+            futures: (MaybeDone<$F1>, $(MaybeDone<$F>),+),
+            finished: bool,
+        }
+
+        impl<$F1, $($F),+> $name<$F1, $($F),+>
+        where
+            $F1: Future,
+            $($F: Future<Error = $F1::Error>,)+
+        {
+            pub fn new($f1: $F1, $($f: $F),+) -> Self {
+                debug!("Creating new {} future", stringify!($name));
+                Self {
+                    futures: (MaybeDone::Future($f1), $(MaybeDone::Future($f)),+),
+                    finished: false,
+                }
+            }
+
+            fn cancel(&mut self) {
+                debug!("Cancelling remaining {} children", stringify!($name));
+                self.futures.$i1.cancel();
+                $(self.futures.$i.cancel();)+
+                self.finished = true;
+            }
+        }
+
+        impl<$F1, $($F),+> Future for $name<$F1, $($F),+>
+        where
+            $F1: Future,
+            $($F: Future<Error = $F1::Error>,)+
+            $F1::Error: From<FutError>,
+            $F1::Output: Debug,
+            $($F::Output: Debug,)+
+        {
+            type Output = ($F1::Output, $($F::Output),+);
+            type Error = $F1::Error;
+
+            fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+                debug!("Polling {} future", stringify!($name));
+                if self.finished {
+                    error!("ERROR: {} future polled after completion!", stringify!($name));
+                    return Err(FutError::PolledAfterCompletion.into());
+                }
+
+                let state = self.futures.$i1.poll()?.state;
+                $(
+                    let state = state.combine(self.futures.$i.poll()?.state);
+                )+
+
+                if state != FutState::Done {
+                    return Ok(FutResult { state, value: None });
+                }
+
+                match (self.futures.$i1.take_output(), $(self.futures.$i.take_output()),+) {
+                    (Some($f1), $(Some($f)),+) => {
+                        self.finished = true;
+                        Ok(FutResult::finished(($f1, $($f),+)))
+                    }
+                    _ => {
+                        error!("ERROR: {} finished without every output!", stringify!($name));
+                        Err(FutError::CompletedWithoutValue.into())
+                    }
+                }
+            }
+
+            fn cleanup(&mut self) {
+                debug!("Destroying {} future", stringify!($name));
+                self.futures.$i1.cleanup();
+                $(self.futures.$i.cleanup();)+
+            }
+        }
+
+        pub struct $try_name<$F1: Future, $($F: Future),+> {
+            join: $name<$F1, $($F),+>,
+        }
+
+        impl<$F1, $($F),+> $try_name<$F1, $($F),+>
+        where
+            $F1: Future,
+            $($F: Future<Error = $F1::Error>,)+
+        {
+            pub fn new($f1: $F1, $($f: $F),+) -> Self {
+                debug!("Creating new {} future", stringify!($try_name));
+                Self {
+                    join: $name::new($f1, $($f),+),
+                }
+            }
+        }
+
+        impl<$F1, $($F),+> Future for $try_name<$F1, $($F),+>
+        where
+            $F1: Future,
+            $($F: Future<Error = $F1::Error>,)+
+            $F1::Error: Debug + From<FutError>,
+            $F1::Output: Debug,
+            $($F::Output: Debug,)+
+        {
+            type Output = ($F1::Output, $($F::Output),+);
+            type Error = $F1::Error;
+
+            fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+                debug!("Polling {} future", stringify!($try_name));
+                self.join.poll().inspect_err(|e| {
+                    error!("{} child failed with {:?}, cancelling siblings", stringify!($try_name), e);
+                    self.join.cancel();
+                })
+            }
+
+            fn cleanup(&mut self) {
+                debug!("Destroying {} future", stringify!($try_name));
+                self.join.cleanup();
+            }
+        }
+    };
+}
+
+join_future!(Join, TryJoin; F1 first 0, F2 second 1);
+join_future!(Join3, TryJoin3; F1 first 0, F2 second 1, F3 third 2);
+join_future!(Join4, TryJoin4; F1 first 0, F2 second 1, F3 third 2, F4 fourth 3);
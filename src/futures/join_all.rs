@@ -0,0 +1,86 @@
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::fmt::Debug;
+
+pub struct JoinAllSettled<F: Future> {
+    futures: Vec<Option<F>>,
+    results: Vec<Option<Result<F::Output, F::Error>>>,
+    finished: bool,
+}
+
+pub fn join_all_settled<F: Future>(futures: Vec<F>) -> JoinAllSettled<F> {
+    debug!(
+        "Creating new JoinAllSettled future over {} futures",
+        futures.len()
+    );
+    JoinAllSettled {
+        results: futures.iter().map(|_| None).collect(),
+        futures: futures.into_iter().map(Some).collect(),
+        finished: false,
+    }
+}
+
+impl<F> Future for JoinAllSettled<F>
+where
+    F: Future,
+    F::Output: Debug,
+    F::Error: Debug + From<FutError>,
+{
+    type Output = Vec<Result<F::Output, F::Error>>;
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling JoinAllSettled future");
+        if self.finished {
+            error!("ERROR: JoinAllSettled future polled after completion!");
+            return Err(FutError::PolledAfterCompletion);
+        }
+
+        let mut state = FutState::Done;
+        for (slot, result) in self.futures.iter_mut().zip(self.results.iter_mut()) {
+            let Some(future) = slot else {
+                continue;
+            };
+
+            let settled = match future.poll() {
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: Some(value),
+                }) => Ok(value),
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: None,
+                }) => Err(FutError::CompletedWithoutValue.into()),
+                Ok(FutResult {
+                    state: child_state, ..
+                }) => {
+                    state = state.combine(child_state);
+                    continue;
+                }
+                Err(e) => {
+                    debug!("JoinAllSettled child settled with error {:?}", e);
+                    Err(e)
+                }
+            };
+
+            future.cleanup();
+            *slot = None;
+            *result = Some(settled);
+        }
+
+        if state != FutState::Done {
+            return Ok(FutResult { state, value: None });
+        }
+
+        self.finished = true;
+        let results = self.results.iter_mut().filter_map(Option::take).collect();
+        Ok(FutResult::finished(results))
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying JoinAllSettled future");
+        for future in self.futures.iter_mut().flatten() {
+            future.cleanup();
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error, warn};
+use std::collections::VecDeque;
+use std::fmt::Debug;
+
+pub struct JoinAllLimited<F: Future> {
+    queued: VecDeque<(usize, F)>,
+    in_flight: Vec<(usize, F)>,
+    outputs: Vec<Option<F::Output>>,
+    limit: usize,
+    finished: bool,
+}
+
+pub fn join_all_limited<F: Future>(futures: Vec<F>, limit: usize) -> JoinAllLimited<F> {
+    debug!(
+        "Creating new JoinAllLimited future over {} futures with limit {}",
+        futures.len(),
+        limit
+    );
+    if limit == 0 {
+        warn!("JoinAllLimited limit of 0 would never make progress, using 1");
+    }
+
+    JoinAllLimited {
+        outputs: futures.iter().map(|_| None).collect(),
+        queued: futures.into_iter().enumerate().collect(),
+        in_flight: Vec::new(),
+        limit: limit.max(1),
+        finished: false,
+    }
+}
+
+impl<F: Future> JoinAllLimited<F> {
+    fn start_queued(&mut self) -> bool {
+        let mut started = false;
+        while self.in_flight.len() < self.limit {
+            let Some((index, future)) = self.queued.pop_front() else {
+                break;
+            };
+            debug!("JoinAllLimited starting child {}", index);
+            self.in_flight.push((index, future));
+            started = true;
+        }
+        started
+    }
+}
+
+impl<F> Future for JoinAllLimited<F>
+where
+    F: Future,
+    F::Output: Debug,
+    F::Error: Debug + From<FutError>,
+{
+    type Output = Vec<F::Output>;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling JoinAllLimited future");
+        if self.finished {
+            error!("ERROR: JoinAllLimited future polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        }
+
+        self.start_queued();
+
+        let mut state = FutState::Done;
+        let mut slot = 0;
+        while slot < self.in_flight.len() {
+            let (index, future) = &mut self.in_flight[slot];
+            let polled = future.poll();
+            let value = match polled {
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: Some(value),
+                }) => value,
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: None,
+                }) => {
+                    error!(
+                        "ERROR: JoinAllLimited child {} completed without value!",
+                        index
+                    );
+                    self.finished = true;
+                    self.cleanup();
+                    return Err(FutError::CompletedWithoutValue.into());
+                }
+                Ok(FutResult {
+                    state: child_state, ..
+                }) => {
+                    state = state.combine(child_state);
+                    slot += 1;
+                    continue;
+                }
+                Err(e) => {
+                    error!("JoinAllLimited child {} failed with {:?}", index, e);
+                    self.finished = true;
+                    self.cleanup();
+                    return Err(e);
+                }
+            };
+
+            let (index, mut future) = self.in_flight.swap_remove(slot);
+            future.cleanup();
+            self.outputs[index] = Some(value);
+        }
+
+        if self.start_queued() {
+            state = FutState::Pending;
+        }
+
+        if state != FutState::Done {
+            return Ok(FutResult { state, value: None });
+        }
+
+        self.finished = true;
+        let outputs = self.outputs.iter_mut().filter_map(Option::take).collect();
+        Ok(FutResult::finished(outputs))
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying JoinAllLimited future");
+        for (_, future) in self.in_flight.iter_mut().chain(self.queued.iter_mut()) {
+            future.cleanup();
+        }
+        self.in_flight.clear();
+        self.queued.clear();
+    }
+}
@@ -0,0 +1,41 @@
+use crate::futures::{FutError, FutResult, Future};
+use log::{debug, error};
+use std::fmt::Debug;
+
+#[derive(Debug, Clone)]
+pub struct Lazy<Fn> {
+    compute: Option<Fn>,
+}
+
+pub fn lazy<Fn, T>(compute: Fn) -> Lazy<Fn>
+where
+    Fn: FnOnce() -> T,
+{
+    debug!("Creating new Lazy future");
+    Lazy {
+        compute: Some(compute),
+    }
+}
+
+impl<Fn, T> Future for Lazy<Fn>
+where
+    Fn: FnOnce() -> T,
+    T: Debug,
+{
+    type Output = T;
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Lazy future");
+        let compute = self.compute.take().ok_or_else(|| {
+            error!("ERROR: Lazy future polled after completion!");
+            FutError::PolledAfterCompletion
+        })?;
+
+        Ok(FutResult::finished(compute()))
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Lazy future");
+    }
+}
@@ -0,0 +1,93 @@
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Loop<B, C> {
+    Break(B),
+    Continue(C),
+}
+
+pub struct LoopFn<Fn, F> {
+    body: Fn,
+    current: Option<F>,
+}
+
+pub fn loop_fn<S, Fn, F, B>(initial: S, mut body: Fn) -> LoopFn<Fn, F>
+where
+    Fn: FnMut(S) -> F,
+    F: Future<Output = Loop<B, S>>,
+{
+    debug!("Creating new LoopFn future");
+    LoopFn {
+        current: Some(body(initial)),
+        body,
+    }
+}
+
+impl<S, Fn, F, B> Future for LoopFn<Fn, F>
+where
+    Fn: FnMut(S) -> F,
+    F: Future<Output = Loop<B, S>>,
+    F::Error: From<FutError>,
+{
+    type Output = B;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling LoopFn future");
+        let Some(future) = self.current.as_mut() else {
+            error!("ERROR: LoopFn future polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        };
+
+        let res = match future.poll() {
+            Ok(res) => res,
+            Err(e) => {
+                self.cleanup();
+                return Err(e);
+            }
+        };
+
+        match res {
+            FutResult {
+                state: FutState::Done,
+                value: Some(step),
+            } => {
+                self.cleanup();
+                match step {
+                    Loop::Break(value) => {
+                        debug!("LoopFn body broke out of the loop");
+                        Ok(FutResult {
+                            state: FutState::Done,
+                            value: Some(value),
+                        })
+                    }
+                    Loop::Continue(state) => {
+                        debug!("LoopFn body continued, starting next iteration");
+                        self.current = Some((self.body)(state));
+                        Ok(FutResult {
+                            state: FutState::Pending,
+                            value: None,
+                        })
+                    }
+                }
+            }
+            FutResult {
+                state: FutState::Done,
+                value: None,
+            } => {
+                error!("ERROR: LoopFn body completed without value!");
+                self.cleanup();
+                Err(FutError::CompletedWithoutValue.into())
+            }
+            FutResult { state, .. } => Ok(FutResult { state, value: None }),
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying LoopFn future");
+        if let Some(mut future) = self.current.take() {
+            future.cleanup();
+        }
+    }
+}
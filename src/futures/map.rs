@@ -0,0 +1,66 @@
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::fmt::Debug;
+
+#[derive(Debug, Clone)]
+pub struct Map<F, Fn> {
+    future: F,
+    transform: Option<Fn>,
+}
+
+impl<F, Fn, T> Map<F, Fn>
+where
+    F: Future,
+    Fn: FnOnce(F::Output) -> T,
+{
+    pub fn new(future: F, transform: Fn) -> Self {
+        debug!("Creating new Map future");
+        Self {
+            future,
+            transform: Some(transform),
+        }
+    }
+}
+
+impl<F, Fn, T> Future for Map<F, Fn>
+where
+    F: Future,
+    F::Error: From<FutError>,
+    F::Output: Debug,
+    Fn: FnOnce(F::Output) -> T,
+    T: Debug,
+{
+    type Output = T;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Map future");
+        match self.future.poll()? {
+            FutResult {
+                state: FutState::Done,
+                value: Some(value),
+            } => {
+                let transform = self.transform.take().ok_or_else(|| {
+                    error!("ERROR: Map future polled after completion!");
+                    FutError::PolledAfterCompletion
+                })?;
+
+                debug!("Map inner future completed with value {:?}", value);
+                Ok(FutResult::finished(transform(value)))
+            }
+            FutResult {
+                state: FutState::Done,
+                value: None,
+            } => {
+                error!("ERROR: Map inner future completed without value!");
+                Err(FutError::CompletedWithoutValue.into())
+            }
+            FutResult { state, .. } => Ok(FutResult { state, value: None }),
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Map future");
+        self.future.cleanup();
+    }
+}
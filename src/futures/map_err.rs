@@ -0,0 +1,43 @@
+use crate::futures::{FutResult, Future};
+use log::{debug, error};
+use std::fmt::Debug;
+
+#[derive(Debug, Clone)]
+pub struct MapErr<F, Fn> {
+    future: F,
+    err_map: Fn,
+}
+
+impl<F, Fn, E> MapErr<F, Fn>
+where
+    F: Future,
+    Fn: FnMut(F::Error) -> E,
+{
+    pub fn new(future: F, err_map: Fn) -> Self {
+        debug!("Creating new MapErr future");
+        Self { future, err_map }
+    }
+}
+
+impl<F, Fn, E> Future for MapErr<F, Fn>
+where
+    F: Future,
+    F::Error: Debug,
+    Fn: FnMut(F::Error) -> E,
+{
+    type Output = F::Output;
+    type Error = E;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling MapErr future");
+        self.future.poll().map_err(|e| {
+            error!("MapErr inner future poll resulted in error {:?}", e);
+            (self.err_map)(e)
+        })
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying MapErr future");
+        self.future.cleanup();
+    }
+}
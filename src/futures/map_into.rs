@@ -0,0 +1,46 @@
+use crate::futures::{FutResult, Future};
+use log::debug;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+pub struct MapInto<F, T> {
+    future: F,
+    _output: PhantomData<T>,
+}
+
+impl<F, T> MapInto<F, T>
+where
+    F: Future,
+    F::Output: Into<T>,
+{
+    pub fn new(future: F) -> Self {
+        debug!("Creating new MapInto future");
+        Self {
+            future,
+            _output: PhantomData,
+        }
+    }
+}
+
+impl<F, T> Future for MapInto<F, T>
+where
+    F: Future,
+    F::Output: Into<T>,
+{
+    type Output = T;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling MapInto future");
+        let FutResult { state, value } = self.future.poll()?;
+        Ok(FutResult {
+            state,
+            value: value.map(Into::into),
+        })
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying MapInto future");
+        self.future.cleanup();
+    }
+}
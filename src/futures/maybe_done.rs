@@ -0,0 +1,92 @@
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::mem;
+
+#[derive(Debug)]
+pub enum MaybeDone<F: Future> {
+    Future(F),
+    Done(F::Output),
+    Gone,
+}
+
+pub fn maybe_done<F: Future>(future: F) -> MaybeDone<F> {
+    debug!("Creating new MaybeDone future");
+    MaybeDone::Future(future)
+}
+
+impl<F: Future> MaybeDone<F> {
+    pub fn is_done(&self) -> bool {
+        matches!(self, MaybeDone::Done(_))
+    }
+
+    pub fn output_mut(&mut self) -> Option<&mut F::Output> {
+        match self {
+            MaybeDone::Done(output) => Some(output),
+            _ => None,
+        }
+    }
+
+    pub fn take_output(&mut self) -> Option<F::Output> {
+        match mem::replace(self, MaybeDone::Gone) {
+            MaybeDone::Done(output) => Some(output),
+            other => {
+                *self = other;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn cancel(&mut self) {
+        if let MaybeDone::Future(future) = self {
+            future.cleanup();
+        }
+        *self = MaybeDone::Gone;
+    }
+}
+
+impl<F> Future for MaybeDone<F>
+where
+    F: Future,
+    F::Error: From<FutError>,
+{
+    type Output = ();
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling MaybeDone future");
+        let future = match self {
+            MaybeDone::Future(future) => future,
+            MaybeDone::Done(_) => return Ok(FutResult::finished(())),
+            MaybeDone::Gone => {
+                error!("ERROR: MaybeDone future polled after its output was taken!");
+                return Err(FutError::PolledAfterCompletion.into());
+            }
+        };
+
+        match future.poll()? {
+            FutResult {
+                state: FutState::Done,
+                value: Some(value),
+            } => {
+                future.cleanup();
+                *self = MaybeDone::Done(value);
+                Ok(FutResult::finished(()))
+            }
+            FutResult {
+                state: FutState::Done,
+                value: None,
+            } => {
+                error!("ERROR: MaybeDone inner future completed without value!");
+                Err(FutError::CompletedWithoutValue.into())
+            }
+            FutResult { state, .. } => Ok(FutResult { state, value: None }),
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying MaybeDone future");
+        if let MaybeDone::Future(future) = self {
+            future.cleanup();
+        }
+    }
+}
@@ -1,13 +1,158 @@
-pub mod fut_test;
+mod abortable;
+mod and_then;
+mod blocking;
+mod cancellation;
+mod catch;
+mod catch_unwind;
+mod circuit_breaker;
+mod debounce;
+mod delay;
+mod either;
+mod err_into;
+mod ext;
+mod from_callback;
+mod fuse;
+mod future_cache;
+mod futures_ordered;
+mod inspect;
+mod join;
+mod join_all;
+mod join_all_limited;
+mod lazy;
+mod loop_fn;
+mod map;
+mod map_err;
+mod map_into;
+mod maybe_done;
+mod never;
+mod now_or_never;
+mod or_else;
+mod pending;
+mod poll_fn;
+mod rate_limit;
+mod ready;
+mod remote_handle;
+mod retry;
+pub mod runtime;
+mod select;
+mod select_all;
+mod select_ok;
+mod shared;
+mod single_flight;
+mod timeout;
+mod try_flatten;
+mod yield_now;
+mod zip;
+
+pub use abortable::{abortable, AbortHandle, Abortable};
+pub use and_then::AndThen;
+pub use blocking::spawn_blocking;
+pub use cancellation::{CancellationToken, Cancelled};
+pub use catch::Catch;
+pub use catch_unwind::CatchUnwind;
+pub use circuit_breaker::{CircuitBreaker, CircuitState, Guarded};
+pub use debounce::{Debounce, DebounceHandle};
+pub use delay::Delay;
+pub use either::Either;
+pub use err_into::ErrInto;
+pub use ext::{BoxFuture, FutureExt};
+pub use from_callback::{from_callback, CallbackHandle, FromCallback};
+pub use fuse::Fuse;
+pub use future_cache::FutureCache;
+pub use futures_ordered::FuturesOrdered;
+pub use inspect::{Inspect, InspectErr};
+pub use join::{Join, Join3, Join4, TryJoin, TryJoin3, TryJoin4};
+pub use join_all::{join_all_settled, JoinAllSettled};
+pub use join_all_limited::{join_all_limited, JoinAllLimited};
+pub use lazy::{lazy, Lazy};
+pub use loop_fn::{loop_fn, Loop, LoopFn};
+pub use map::Map;
+pub use map_err::MapErr;
+pub use map_into::MapInto;
+pub use maybe_done::{maybe_done, MaybeDone};
+pub use never::Never;
+pub use now_or_never::now_or_never;
+pub use or_else::OrElse;
+pub use pending::{pending, Pending};
+pub use poll_fn::{poll_fn, PollFn};
+pub use rate_limit::{RateLimited, RateLimiter};
+pub use ready::{ready, Ready};
+pub use remote_handle::{remote_handle, Remote, RemoteHandle};
+pub use retry::{BackoffPolicy, ExponentialBackoff, FixedBackoff, Retry};
+pub use runtime::{block_on, init_logging, JoinHandle, Priority, Waker};
+pub use select::{Race, Select};
+pub use select_all::{select_all, SelectAll};
+pub use select_ok::{select_ok, SelectOk};
+pub use shared::{Shared, WeakShared};
+pub use single_flight::SingleFlight;
+pub use timeout::Timeout;
+pub use try_flatten::TryFlatten;
+pub use yield_now::{yield_now, YieldNow};
+pub use zip::{zip, Zip};
 
 use log::{debug, error};
+use runtime::report_violation;
 use std::{fmt::Debug, mem};
 
-#[derive(Debug)]
+pub type TaskId = usize;
+
+#[derive(Debug, Clone)]
 pub enum FutError {
     SleepingUnsupported,
     PolledAfterCompletion,
     CompletedWithoutValue,
+    ResultUnavailable(TaskId),
+    ResultTypeMismatch {
+        task: TaskId,
+        expected: &'static str,
+    },
+    ContractViolation {
+        task: TaskId,
+        violation: ContractViolation,
+    },
+    NoActiveRunner,
+    TimedOut,
+    Aborted,
+    Panicked(String),
+    CircuitOpen,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ErrorKind {
+    Timeout,
+    Cancelled,
+    Resource,
+    Protocol,
+    Internal,
+}
+
+impl FutError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            FutError::PolledAfterCompletion
+            | FutError::CompletedWithoutValue
+            | FutError::ContractViolation { .. } => ErrorKind::Protocol,
+            FutError::TimedOut => ErrorKind::Timeout,
+            FutError::Aborted => ErrorKind::Cancelled,
+            FutError::NoActiveRunner | FutError::CircuitOpen => ErrorKind::Resource,
+            FutError::SleepingUnsupported
+            | FutError::ResultUnavailable(_)
+            | FutError::ResultTypeMismatch { .. }
+            | FutError::Panicked(_) => ErrorKind::Internal,
+        }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.kind(), ErrorKind::Timeout | ErrorKind::Resource)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ContractViolation {
+    PolledAfterCompletion,
+    DoneWithoutValue,
+    WaitingWithoutWakeSource,
+    CleanupCalledTwice,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -17,6 +162,16 @@ pub enum FutState {
     Waiting,
 }
 
+impl FutState {
+    pub(crate) fn combine(self, other: FutState) -> FutState {
+        match (self, other) {
+            (FutState::Pending, _) | (_, FutState::Pending) => FutState::Pending,
+            (FutState::Waiting, _) | (_, FutState::Waiting) => FutState::Waiting,
+            _ => FutState::Done,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct FutResult<T> {
     pub state: FutState,
@@ -49,15 +204,32 @@ pub trait Future {
     fn cleanup(&mut self);
 }
 
+impl<F: Future + ?Sized> Future for Box<F> {
+    type Output = F::Output;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        (**self).poll()
+    }
+
+    fn cleanup(&mut self) {
+        (**self).cleanup()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Done<T> {
     res: Option<T>,
+    cleaned: bool,
 }
 
 impl<T: Debug> Done<T> {
     pub fn new(val: T) -> Self {
         debug!("Creating new Done future with value {:?}", val);
-        Self { res: Some(val) }
+        Self {
+            res: Some(val),
+            cleaned: false,
+        }
     }
 }
 
@@ -75,6 +247,11 @@ impl<T: Clone + Debug> Future for Done<T> {
     }
 
     fn cleanup(&mut self) {
+        if mem::replace(&mut self.cleaned, true) {
+            error!("ERROR: Done future cleaned up twice!");
+            report_violation(ContractViolation::CleanupCalledTwice);
+            return;
+        }
         debug!("Destroying Done future");
     }
 }
@@ -82,30 +259,42 @@ impl<T: Clone + Debug> Future for Done<T> {
 #[derive(Debug, Clone)]
 pub struct Failed<T> {
     err: Option<T>,
+    cleaned: bool,
 }
 
 impl<T: Debug> Failed<T> {
     pub fn _new(err: T) -> Self {
         debug!("Creating new Reject future with err {:?}", err);
-        Self { err: Some(err) }
+        Self {
+            err: Some(err),
+            cleaned: false,
+        }
     }
 }
 
-impl<T: Clone> Future for Failed<T> {
+impl<T: From<FutError>> Future for Failed<T> {
     type Output = ();
     type Error = T;
 
     fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
         debug!("Polling Reject future");
 
-        let result = Err(self.err.take().expect("Reject polled"));
+        let Some(err) = self.err.take() else {
+            error!("ERROR: Reject future polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        };
         error!("Reject future poll resulted in error");
 
-        result
+        Err(err)
     }
 
     fn cleanup(&mut self) {
-        println!("Destroying Reject future");
+        if mem::replace(&mut self.cleaned, true) {
+            error!("ERROR: Reject future cleaned up twice!");
+            report_violation(ContractViolation::CleanupCalledTwice);
+            return;
+        }
+        debug!("Destroying Reject future");
     }
 }
 
@@ -122,37 +311,57 @@ where
 }
 
 #[derive(Debug, Clone)]
-pub struct Chain<F1, F2, Fn>
+pub struct Chain<F1, F2, Fn, M = fn(<F1 as Future>::Error) -> <F1 as Future>::Error>
 where
     F1: Future,
     F2: Future,
     Fn: FnOnce(F1::Output) -> F2,
 {
     state: ChainState<F1, F2, Fn>,
+    err_map: M,
 }
 
 impl<F1, F2, Fn> Chain<F1, F2, Fn>
 where
-    F1: Future + Debug,
+    F1: Future,
     F2: Future,
     Fn: FnOnce(F1::Output) -> F2,
 {
     pub fn new(future: F1, transform: Fn) -> Self {
-        debug!("Creating new Chain future having future {:?}", future);
+        debug!("Creating new Chain future");
         Self {
             state: ChainState::First { future, transform },
+            err_map: |err| err,
         }
     }
 }
 
-impl<F1, F2, Fn> Future for Chain<F1, F2, Fn>
+impl<F1, F2, Fn, M> Chain<F1, F2, Fn, M>
 where
     F1: Future,
-    F2: Future<Error = F1::Error>,
+    F2: Future,
+    Fn: FnOnce(F1::Output) -> F2,
+    M: FnMut(F2::Error) -> F1::Error,
+{
+    pub fn new_with_err_map(future: F1, transform: Fn, err_map: M) -> Self {
+        debug!("Creating new Chain future with error map");
+        Self {
+            state: ChainState::First { future, transform },
+            err_map,
+        }
+    }
+}
+
+impl<F1, F2, Fn, M> Future for Chain<F1, F2, Fn, M>
+where
+    F1: Future,
+    F2: Future,
     F1::Error: std::fmt::Debug + From<FutError>,
+    F2::Error: Debug,
     F2::Output: Debug,
     F1::Output: Debug,
     Fn: FnOnce(F1::Output) -> F2 + Clone,
+    M: FnMut(F2::Error) -> F1::Error,
 {
     type Output = F2::Output;
     type Error = F1::Error;
@@ -221,7 +430,7 @@ where
                     Err(e) => {
                         error!("Second future poll resulted in error {:?}", e);
                         self.state = ChainState::Second(future);
-                        Err(e)
+                        Err((self.err_map)(e))
                     }
                 }
             }
@@ -0,0 +1,24 @@
+use crate::futures::{FutError, FutResult, FutState, Future, Waker};
+use log::debug;
+use std::convert::Infallible;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Never;
+
+impl Future for Never {
+    type Output = Infallible;
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Never future");
+        let state = match Waker::current() {
+            Some(_) => FutState::Waiting,
+            None => FutState::Pending,
+        };
+        Ok(FutResult { state, value: None })
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Never future");
+    }
+}
@@ -0,0 +1,19 @@
+use crate::futures::{FutResult, FutState, Future};
+use log::debug;
+
+pub fn now_or_never<F: Future>(mut future: F) -> Result<Option<F::Output>, F::Error> {
+    debug!("Polling future once for now_or_never");
+    let res = future.poll();
+    future.cleanup();
+
+    match res? {
+        FutResult {
+            state: FutState::Done,
+            value,
+        } => Ok(value),
+        FutResult { state, .. } => {
+            debug!("now_or_never future was not ready: {:?}", state);
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::{fmt::Debug, mem};
+
+#[derive(Debug, Clone)]
+enum OrElseState<F1, F2, Fn> {
+    First { future: F1, fallback: Fn },
+    Second(F2),
+    Done,
+}
+
+#[derive(Debug, Clone)]
+pub struct OrElse<F1, F2, Fn> {
+    state: OrElseState<F1, F2, Fn>,
+}
+
+impl<F1, F2, Fn> OrElse<F1, F2, Fn>
+where
+    F1: Future,
+    F2: Future<Output = F1::Output>,
+    Fn: FnOnce(F1::Error) -> F2,
+{
+    pub fn new(future: F1, fallback: Fn) -> Self {
+        debug!("Creating new OrElse future");
+        Self {
+            state: OrElseState::First { future, fallback },
+        }
+    }
+}
+
+impl<F1, F2, Fn> Future for OrElse<F1, F2, Fn>
+where
+    F1: Future,
+    F2: Future<Output = F1::Output>,
+    F1::Error: Debug,
+    F2::Error: Debug + From<FutError>,
+    Fn: FnOnce(F1::Error) -> F2,
+{
+    type Output = F1::Output;
+    type Error = F2::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling OrElse future");
+        match mem::replace(&mut self.state, OrElseState::Done) {
+            OrElseState::First {
+                mut future,
+                fallback,
+            } => match future.poll() {
+                Ok(res) if res.state == FutState::Done => {
+                    future.cleanup();
+                    Ok(res)
+                }
+                Ok(res) => {
+                    self.state = OrElseState::First { future, fallback };
+                    Ok(res)
+                }
+                Err(e) => {
+                    debug!("OrElse first future failed, running fallback: {:?}", e);
+                    future.cleanup();
+                    self.state = OrElseState::Second(fallback(e));
+                    Ok(FutResult {
+                        state: FutState::Pending,
+                        value: None,
+                    })
+                }
+            },
+            OrElseState::Second(mut future) => match future.poll() {
+                Ok(res) if res.state == FutState::Done => {
+                    future.cleanup();
+                    Ok(res)
+                }
+                Ok(res) => {
+                    self.state = OrElseState::Second(future);
+                    Ok(res)
+                }
+                Err(e) => {
+                    error!("OrElse fallback future poll resulted in error {:?}", e);
+                    future.cleanup();
+                    Err(e)
+                }
+            },
+            OrElseState::Done => {
+                error!("ERROR: OrElse future polled after completion!");
+                Err(FutError::PolledAfterCompletion.into())
+            }
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying OrElse future");
+        match self.state {
+            OrElseState::First { ref mut future, .. } => future.cleanup(),
+            OrElseState::Second(ref mut future) => future.cleanup(),
+            OrElseState::Done => {}
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::futures::{FutError, FutResult, FutState, Future, Waker};
+use log::debug;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Pending<T> {
+    _output: PhantomData<T>,
+}
+
+pub fn pending<T>() -> Pending<T> {
+    debug!("Creating new Pending future");
+    Pending {
+        _output: PhantomData,
+    }
+}
+
+impl<T> Future for Pending<T> {
+    type Output = T;
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Pending future");
+        let state = match Waker::current() {
+            Some(_) => FutState::Waiting,
+            None => FutState::Pending,
+        };
+        Ok(FutResult { state, value: None })
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Pending future");
+    }
+}
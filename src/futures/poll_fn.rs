@@ -0,0 +1,32 @@
+use crate::futures::{FutResult, Future};
+use log::debug;
+
+#[derive(Debug, Clone)]
+pub struct PollFn<Fn> {
+    poll: Fn,
+}
+
+pub fn poll_fn<Fn, T, E>(poll: Fn) -> PollFn<Fn>
+where
+    Fn: FnMut() -> Result<FutResult<T>, E>,
+{
+    debug!("Creating new PollFn future");
+    PollFn { poll }
+}
+
+impl<Fn, T, E> Future for PollFn<Fn>
+where
+    Fn: FnMut() -> Result<FutResult<T>, E>,
+{
+    type Output = T;
+    type Error = E;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling PollFn future");
+        (self.poll)()
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying PollFn future");
+    }
+}
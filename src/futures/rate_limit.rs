@@ -0,0 +1,114 @@
+use crate::futures::runtime::wake_at;
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    tokens: f64,
+    refill: Duration,
+    last: Instant,
+}
+
+impl Bucket {
+    fn try_acquire(&mut self) -> Result<(), Instant> {
+        let now = Instant::now();
+        let refilled = now.duration_since(self.last).as_secs_f64() / self.refill.as_secs_f64();
+        self.tokens = (self.tokens + refilled).min(self.capacity);
+        self.last = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+
+        Err(now + self.refill.mul_f64(1.0 - self.tokens))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    bucket: Rc<RefCell<Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(permits: usize, interval: Duration) -> Self {
+        debug!(
+            "Creating new RateLimiter allowing {} permits per {:?}",
+            permits, interval
+        );
+        let permits = permits.max(1);
+        Self {
+            bucket: Rc::new(RefCell::new(Bucket {
+                capacity: permits as f64,
+                tokens: permits as f64,
+                refill: interval / permits as u32,
+                last: Instant::now(),
+            })),
+        }
+    }
+
+    pub fn limit<F: Future>(&self, future: F) -> RateLimited<F> {
+        RateLimited {
+            future: Some(future),
+            limiter: self.clone(),
+            permitted: false,
+        }
+    }
+}
+
+pub struct RateLimited<F> {
+    future: Option<F>,
+    limiter: RateLimiter,
+    permitted: bool,
+}
+
+impl<F> Future for RateLimited<F>
+where
+    F: Future,
+    F::Error: From<FutError>,
+{
+    type Output = F::Output;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling RateLimited future");
+        let Some(future) = self.future.as_mut() else {
+            error!("ERROR: RateLimited future polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        };
+
+        if !self.permitted {
+            let acquired = self.limiter.bucket.borrow_mut().try_acquire();
+            match acquired {
+                Ok(()) => {
+                    debug!("RateLimited future acquired a permit");
+                    self.permitted = true;
+                }
+                Err(next_permit) => {
+                    wake_at(next_permit);
+                    return Ok(FutResult {
+                        state: FutState::Waiting,
+                        value: None,
+                    });
+                }
+            }
+        }
+
+        let res = future.poll();
+        if res.as_ref().map_or(true, |res| res.state == FutState::Done) {
+            self.cleanup();
+        }
+        res
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying RateLimited future");
+        if let Some(mut future) = self.future.take() {
+            future.cleanup();
+        }
+    }
+}
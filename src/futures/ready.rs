@@ -0,0 +1,34 @@
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+
+#[derive(Debug, Clone)]
+pub struct Ready<T> {
+    value: Option<T>,
+}
+
+pub fn ready<T>(value: T) -> Ready<T> {
+    debug!("Creating new Ready future");
+    Ready { value: Some(value) }
+}
+
+impl<T> Future for Ready<T> {
+    type Output = T;
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Ready future");
+        let value = self.value.take().ok_or_else(|| {
+            error!("ERROR: Ready future polled after completion!");
+            FutError::PolledAfterCompletion
+        })?;
+
+        Ok(FutResult {
+            state: FutState::Done,
+            value: Some(value),
+        })
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Ready future");
+    }
+}
@@ -0,0 +1,161 @@
+use crate::futures::runtime::Waker;
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+struct Slot<T, E> {
+    result: Option<Result<T, E>>,
+    waker: Option<Waker>,
+    detached: bool,
+}
+
+impl<T, E> Slot<T, E> {
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+pub struct Remote<F: Future> {
+    future: Option<F>,
+    slot: Rc<RefCell<Slot<F::Output, F::Error>>>,
+}
+
+pub struct RemoteHandle<T, E> {
+    slot: Rc<RefCell<Slot<T, E>>>,
+    finished: bool,
+}
+
+pub fn remote_handle<F: Future>(future: F) -> (Remote<F>, RemoteHandle<F::Output, F::Error>) {
+    debug!("Creating new Remote future and handle");
+    let slot = Rc::new(RefCell::new(Slot {
+        result: None,
+        waker: None,
+        detached: false,
+    }));
+
+    let remote = Remote {
+        future: Some(future),
+        slot: Rc::clone(&slot),
+    };
+    let handle = RemoteHandle {
+        slot,
+        finished: false,
+    };
+
+    (remote, handle)
+}
+
+impl<F> Future for Remote<F>
+where
+    F: Future,
+    F::Error: From<FutError>,
+{
+    type Output = ();
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Remote future");
+        let Some(future) = self.future.as_mut() else {
+            error!("ERROR: Remote future polled after completion!");
+            return Err(FutError::PolledAfterCompletion);
+        };
+
+        if Rc::strong_count(&self.slot) == 1 && !self.slot.borrow().detached {
+            debug!("RemoteHandle dropped, abandoning the remote future");
+            self.cleanup();
+            return Ok(FutResult::finished(()));
+        }
+
+        let result = match future.poll() {
+            Ok(FutResult {
+                state: FutState::Done,
+                value: Some(value),
+            }) => Ok(value),
+            Ok(FutResult {
+                state: FutState::Done,
+                value: None,
+            }) => Err(FutError::CompletedWithoutValue.into()),
+            Ok(FutResult { state, .. }) => return Ok(FutResult { state, value: None }),
+            Err(e) => Err(e),
+        };
+
+        debug!("Remote future settled, handing the result to its handle");
+        self.cleanup();
+        let mut slot = self.slot.borrow_mut();
+        slot.result = Some(result);
+        slot.wake();
+        Ok(FutResult::finished(()))
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Remote future");
+        if let Some(mut future) = self.future.take() {
+            future.cleanup();
+        }
+    }
+}
+
+impl<F: Future> Drop for Remote<F> {
+    fn drop(&mut self) {
+        self.slot.borrow_mut().wake();
+    }
+}
+
+impl<T, E> RemoteHandle<T, E> {
+    pub fn forget(self) {
+        debug!("RemoteHandle detached, remote future keeps running");
+        self.slot.borrow_mut().detached = true;
+    }
+}
+
+impl<T, E> Future for RemoteHandle<T, E>
+where
+    E: From<FutError>,
+{
+    type Output = T;
+    type Error = E;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling RemoteHandle future");
+        if self.finished {
+            error!("ERROR: RemoteHandle polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        }
+
+        let mut slot = self.slot.borrow_mut();
+        match slot.result.take() {
+            Some(result) => {
+                self.finished = true;
+                result.map(|value| FutResult {
+                    state: FutState::Done,
+                    value: Some(value),
+                })
+            }
+            None if Rc::strong_count(&self.slot) == 1 => {
+                error!("ERROR: Remote future dropped before producing a result!");
+                self.finished = true;
+                Err(FutError::CompletedWithoutValue.into())
+            }
+            None => match Waker::current() {
+                Some(waker) => {
+                    slot.waker = Some(waker);
+                    Ok(FutResult {
+                        state: FutState::Waiting,
+                        value: None,
+                    })
+                }
+                None => Ok(FutResult {
+                    state: FutState::Pending,
+                    value: None,
+                }),
+            },
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying RemoteHandle future");
+    }
+}
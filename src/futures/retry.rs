@@ -0,0 +1,192 @@
+use crate::futures::{Delay, FutError, FutResult, FutState, Future};
+use log::{debug, error, warn};
+use std::fmt::Debug;
+use std::time::Duration;
+
+pub trait BackoffPolicy {
+    fn next_delay(&mut self, failures: usize) -> Option<Duration>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FixedBackoff {
+    delay: Duration,
+    max_attempts: usize,
+}
+
+impl FixedBackoff {
+    pub fn new(delay: Duration, max_attempts: usize) -> Self {
+        Self {
+            delay,
+            max_attempts,
+        }
+    }
+}
+
+impl BackoffPolicy for FixedBackoff {
+    fn next_delay(&mut self, failures: usize) -> Option<Duration> {
+        (failures < self.max_attempts).then_some(self.delay)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ExponentialBackoff {
+    base: Duration,
+    max_delay: Duration,
+    max_attempts: usize,
+}
+
+impl ExponentialBackoff {
+    pub fn new(base: Duration, max_attempts: usize) -> Self {
+        Self {
+            base,
+            max_delay: Duration::MAX,
+            max_attempts,
+        }
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+}
+
+impl BackoffPolicy for ExponentialBackoff {
+    fn next_delay(&mut self, failures: usize) -> Option<Duration> {
+        if failures >= self.max_attempts {
+            return None;
+        }
+
+        let factor = 2u32.saturating_pow(failures.saturating_sub(1) as u32);
+        Some(
+            self.base
+                .checked_mul(factor)
+                .unwrap_or(Duration::MAX)
+                .min(self.max_delay),
+        )
+    }
+}
+
+pub struct Retry<Fn, F, P, R = fn(&<F as Future>::Error) -> bool>
+where
+    F: Future,
+{
+    factory: Fn,
+    policy: P,
+    retry_if: R,
+    current: Option<F>,
+    backoff: Option<Delay>,
+    failures: usize,
+    finished: bool,
+}
+
+impl<Fn, F, P> Retry<Fn, F, P>
+where
+    Fn: FnMut() -> F,
+    F: Future<Error = FutError>,
+    P: BackoffPolicy,
+{
+    pub fn new(factory: Fn, policy: P) -> Self {
+        debug!("Creating new Retry future");
+        Self::new_with_predicate(factory, policy, FutError::is_retryable)
+    }
+}
+
+impl<Fn, F, P, R> Retry<Fn, F, P, R>
+where
+    Fn: FnMut() -> F,
+    F: Future,
+    P: BackoffPolicy,
+    R: FnMut(&F::Error) -> bool,
+{
+    pub fn new_with_predicate(factory: Fn, policy: P, retry_if: R) -> Self {
+        debug!("Creating new Retry future with retry predicate");
+        Self {
+            factory,
+            policy,
+            retry_if,
+            current: None,
+            backoff: None,
+            failures: 0,
+            finished: false,
+        }
+    }
+}
+
+impl<Fn, F, P, R> Future for Retry<Fn, F, P, R>
+where
+    Fn: FnMut() -> F,
+    F: Future,
+    F::Error: Debug + From<FutError>,
+    P: BackoffPolicy,
+    R: FnMut(&F::Error) -> bool,
+{
+    type Output = F::Output;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Retry future");
+        if self.finished {
+            error!("ERROR: Retry future polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        }
+
+        if let Some(backoff) = self.backoff.as_mut() {
+            if backoff.poll()?.state != FutState::Done {
+                return Ok(FutResult {
+                    state: FutState::Waiting,
+                    value: None,
+                });
+            }
+            self.backoff = None;
+        }
+
+        let future = self.current.get_or_insert_with(&mut self.factory);
+        match future.poll() {
+            Ok(res) => {
+                if res.state == FutState::Done {
+                    self.finished = true;
+                    self.cleanup();
+                }
+                Ok(res)
+            }
+            Err(e) => {
+                self.cleanup();
+                self.failures += 1;
+                if !(self.retry_if)(&e) {
+                    error!(
+                        "Retry attempt {} failed with non-retryable {:?}",
+                        self.failures, e
+                    );
+                    self.finished = true;
+                    return Err(e);
+                }
+
+                match self.policy.next_delay(self.failures) {
+                    Some(delay) => {
+                        warn!(
+                            "Retry attempt {} failed with {:?}, retrying in {:?}",
+                            self.failures, e, delay
+                        );
+                        self.backoff = Some(Delay::new(delay));
+                        Ok(FutResult {
+                            state: FutState::Pending,
+                            value: None,
+                        })
+                    }
+                    None => {
+                        error!("Retry giving up after {} attempts", self.failures);
+                        self.finished = true;
+                        Err(e)
+                    }
+                }
+            }
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Retry future");
+        if let Some(mut future) = self.current.take() {
+            future.cleanup();
+        }
+    }
+}
@@ -0,0 +1,1382 @@
+use crate::futures::{ContractViolation, FutError, FutResult, FutState, Future, TaskId};
+use log::{debug, error, warn};
+use simple_logger::SimpleLogger;
+use std::any::{type_name, Any, TypeId};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::marker::PhantomData;
+use std::mem;
+use std::rc::Rc;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub trait FutureRunner {
+    fn schedule<F>(&mut self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future<Error = FutError> + 'static,
+        F::Output: 'static;
+
+    fn is_empty(&self) -> bool;
+    fn run(&mut self) -> Result<(), FutError>;
+    fn take_result<T: 'static>(&mut self, id: TaskId) -> Result<T, FutError>;
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RunnerCapacity {
+    pub tasks: usize,
+    pub results: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FutureSize {
+    pub type_name: &'static str,
+    pub size: usize,
+    pub count: usize,
+}
+
+#[derive(Default)]
+struct SizeReport {
+    enabled: bool,
+    sizes: HashMap<&'static str, FutureSize>,
+}
+
+impl SizeReport {
+    fn record<F>(&mut self) {
+        if !self.enabled {
+            return;
+        }
+
+        let type_name = type_name::<F>();
+        let size = mem::size_of::<F>();
+        debug!("Scheduling {} byte future {}", size, type_name);
+        self.sizes
+            .entry(type_name)
+            .or_insert(FutureSize {
+                type_name,
+                size,
+                count: 0,
+            })
+            .count += 1;
+    }
+
+    fn report(&self) -> Vec<FutureSize> {
+        let mut report: Vec<_> = self.sizes.values().cloned().collect();
+        report.sort_by(|a, b| b.size.cmp(&a.size).then(a.type_name.cmp(b.type_name)));
+        report
+    }
+}
+
+#[derive(Default)]
+struct ResultSlots {
+    completed: HashMap<TaskId, Result<Box<dyn Any>, FutError>>,
+    waiters: HashMap<TaskId, Waker>,
+    running: HashSet<TaskId>,
+    detached: HashSet<TaskId>,
+}
+
+#[derive(Default, Clone)]
+struct TaskResults {
+    slots: Rc<RefCell<ResultSlots>>,
+}
+
+impl TaskResults {
+    fn reserve(&mut self, additional: usize) {
+        self.slots.borrow_mut().completed.reserve(additional);
+    }
+
+    fn capacity(&self) -> usize {
+        self.slots.borrow().completed.capacity()
+    }
+
+    fn start(&self, id: TaskId) {
+        let mut slots = self.slots.borrow_mut();
+        slots.running.insert(id);
+        slots.detached.insert(id);
+    }
+
+    fn attach(&self, id: TaskId) {
+        self.slots.borrow_mut().detached.remove(&id);
+    }
+
+    fn detach(&self, id: TaskId) {
+        let mut slots = self.slots.borrow_mut();
+        slots.waiters.remove(&id);
+        if slots.running.contains(&id) {
+            slots.detached.insert(id);
+        } else if slots.completed.remove(&id).is_some() {
+            debug!("Discarding unclaimed result of task {}", id);
+        }
+    }
+
+    fn insert(&self, id: TaskId, result: Result<Box<dyn Any>, FutError>) {
+        let waiter = {
+            let mut slots = self.slots.borrow_mut();
+            slots.running.remove(&id);
+            if slots.detached.remove(&id) {
+                debug!("Task {} is detached, discarding its result", id);
+                return;
+            }
+            slots.completed.insert(id, result);
+            slots.waiters.remove(&id)
+        };
+
+        if let Some(waiter) = waiter {
+            waiter.wake();
+        }
+    }
+
+    fn store(&mut self, id: TaskId, value: Option<AnyOutput>) {
+        self.insert(id, value.ok_or(FutError::CompletedWithoutValue));
+    }
+
+    fn fail(&mut self, id: TaskId, err: FutError) {
+        self.insert(id, Err(err));
+    }
+
+    fn violate(&mut self, task: TaskId, violation: ContractViolation) {
+        error!(
+            "Task {} violated the future contract: {:?}",
+            task, violation
+        );
+        self.fail(task, FutError::ContractViolation { task, violation });
+    }
+
+    fn take<T: 'static>(&self, id: TaskId) -> Result<T, FutError> {
+        let mut slots = self.slots.borrow_mut();
+        let value = slots
+            .completed
+            .remove(&id)
+            .ok_or(FutError::ResultUnavailable(id))??;
+
+        match value.downcast::<T>() {
+            Ok(val) => Ok(*val),
+            Err(value) => {
+                slots.completed.insert(id, Ok(value));
+                Err(FutError::ResultTypeMismatch {
+                    task: id,
+                    expected: type_name::<T>(),
+                })
+            }
+        }
+    }
+
+    fn wait(&self, id: TaskId, waker: Waker) {
+        self.slots.borrow_mut().waiters.insert(id, waker);
+    }
+}
+
+pub struct JoinHandle<T> {
+    id: TaskId,
+    results: TaskResults,
+    finished: bool,
+    _output: PhantomData<T>,
+}
+
+impl<T: 'static> JoinHandle<T> {
+    fn new(id: TaskId, results: &TaskResults) -> Self {
+        results.attach(id);
+        Self {
+            id,
+            results: results.clone(),
+            finished: false,
+            _output: PhantomData,
+        }
+    }
+
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    pub fn try_result(&self) -> Result<T, FutError> {
+        self.results.take(self.id)
+    }
+}
+
+impl<T: 'static> Future for JoinHandle<T> {
+    type Output = T;
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling JoinHandle for task {}", self.id);
+        if self.finished {
+            error!("ERROR: JoinHandle future polled after completion!");
+            return Err(FutError::PolledAfterCompletion);
+        }
+
+        match self.results.take(self.id) {
+            Ok(value) => {
+                self.finished = true;
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: Some(value),
+                })
+            }
+            Err(FutError::ResultUnavailable(_)) => match Waker::current() {
+                Some(waker) => {
+                    self.results.wait(self.id, waker);
+                    Ok(FutResult {
+                        state: FutState::Waiting,
+                        value: None,
+                    })
+                }
+                None => Ok(FutResult {
+                    state: FutState::Pending,
+                    value: None,
+                }),
+            },
+            Err(e) => {
+                self.finished = true;
+                Err(e)
+            }
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying JoinHandle for task {}", self.id);
+    }
+}
+
+impl<T> Drop for JoinHandle<T> {
+    fn drop(&mut self) {
+        self.results.detach(self.id);
+    }
+}
+
+type Hook = Box<dyn Future<Output = (), Error = FutError>>;
+type AnyOutput = Box<dyn Any>;
+type BoxedFuture = Box<dyn Future<Output = AnyOutput, Error = FutError>>;
+
+struct TaskFuture<F> {
+    future: F,
+}
+
+impl<F> TaskFuture<F>
+where
+    F: Future<Error = FutError> + 'static,
+    F::Output: 'static,
+{
+    fn boxed(future: F) -> BoxedFuture {
+        Box::new(Self { future })
+    }
+}
+
+impl<F> Future for TaskFuture<F>
+where
+    F: Future<Error = FutError>,
+    F::Output: 'static,
+{
+    type Output = AnyOutput;
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        let res = self.future.poll()?;
+        Ok(FutResult {
+            state: res.state,
+            value: res.value.map(|value| Box::new(value) as AnyOutput),
+        })
+    }
+
+    fn cleanup(&mut self) {
+        self.future.cleanup();
+    }
+}
+
+thread_local! {
+    static SPAWNED: RefCell<Option<Vec<BoxedFuture>>> = const { RefCell::new(None) };
+    static WAKE_AT: RefCell<Option<Instant>> = const { RefCell::new(None) };
+    static EXTENSIONS: RefCell<Extensions> = RefCell::new(Extensions::new());
+    static WAKER: RefCell<Option<Waker>> = const { RefCell::new(None) };
+    static WAKER_TAKEN: Cell<bool> = const { Cell::new(false) };
+    static VIOLATION: Cell<Option<ContractViolation>> = const { Cell::new(None) };
+}
+
+type Extensions = HashMap<TypeId, Rc<dyn Any>>;
+
+pub struct Context;
+
+impl Context {
+    pub fn extension<T: 'static>() -> Option<Rc<T>> {
+        EXTENSIONS.with(|extensions| {
+            let extension = extensions.borrow().get(&TypeId::of::<T>()).cloned()?;
+            extension.downcast::<T>().ok()
+        })
+    }
+}
+
+struct ExtensionScope<'a> {
+    extensions: &'a mut Extensions,
+}
+
+impl<'a> ExtensionScope<'a> {
+    fn enter(extensions: &'a mut Extensions) -> Self {
+        EXTENSIONS.with(|current| mem::swap(&mut *current.borrow_mut(), extensions));
+        Self { extensions }
+    }
+}
+
+impl Drop for ExtensionScope<'_> {
+    fn drop(&mut self) {
+        EXTENSIONS.with(|current| mem::swap(&mut *current.borrow_mut(), self.extensions));
+    }
+}
+
+pub fn wake_at(deadline: Instant) {
+    WAKE_AT.with(|wake| {
+        let mut wake = wake.borrow_mut();
+        if wake.is_none_or(|current| deadline < current) {
+            debug!("Registering wakeup in {:?}", deadline - Instant::now());
+            *wake = Some(deadline);
+        }
+    });
+}
+
+fn take_wake_at() -> Option<Instant> {
+    WAKE_AT.with(|wake| wake.borrow_mut().take())
+}
+
+pub(crate) fn report_violation(violation: ContractViolation) {
+    VIOLATION.set(Some(violation));
+}
+
+fn take_violation() -> Option<ContractViolation> {
+    VIOLATION.take()
+}
+
+#[derive(Debug, Default)]
+struct WakeQueue {
+    woken: Mutex<HashSet<TaskId>>,
+    ready: Condvar,
+}
+
+impl WakeQueue {
+    fn lock(&self) -> MutexGuard<'_, HashSet<TaskId>> {
+        self.woken.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn take(&self) -> HashSet<TaskId> {
+        mem::take(&mut *self.lock())
+    }
+
+    fn wait(&self, deadline: Option<Instant>) {
+        let mut woken = self.lock();
+        while woken.is_empty() {
+            woken = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if deadline <= now {
+                        break;
+                    }
+                    self.ready
+                        .wait_timeout(woken, deadline - now)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self
+                    .ready
+                    .wait(woken)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum WakeTarget {
+    Task { task: TaskId, queue: Arc<WakeQueue> },
+    Set(WakerSet),
+}
+
+#[derive(Debug, Clone)]
+pub struct Waker {
+    target: WakeTarget,
+}
+
+impl Waker {
+    fn task(task: TaskId, queue: &Arc<WakeQueue>) -> Self {
+        Self {
+            target: WakeTarget::Task {
+                task,
+                queue: Arc::clone(queue),
+            },
+        }
+    }
+
+    pub fn current() -> Option<Waker> {
+        let waker = WAKER.with(|waker| waker.borrow().clone())?;
+        WAKER_TAKEN.set(true);
+        Some(waker)
+    }
+
+    pub fn wake(&self) {
+        match &self.target {
+            WakeTarget::Task { task, queue } => {
+                debug!("Waking task {}", task);
+                queue.lock().insert(*task);
+                queue.ready.notify_one();
+            }
+            WakeTarget::Set(set) => set.wake_all(),
+        }
+    }
+
+    pub fn will_wake(&self, other: &Waker) -> bool {
+        match (&self.target, &other.target) {
+            (
+                WakeTarget::Task { task, queue },
+                WakeTarget::Task {
+                    task: other_task,
+                    queue: other_queue,
+                },
+            ) => task == other_task && Arc::ptr_eq(queue, other_queue),
+            (WakeTarget::Set(set), WakeTarget::Set(other)) => {
+                Arc::ptr_eq(&set.wakers, &other.wakers)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct WakerSet {
+    wakers: Arc<Mutex<Vec<Waker>>>,
+}
+
+impl WakerSet {
+    fn lock(&self) -> MutexGuard<'_, Vec<Waker>> {
+        self.wakers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn register(&self, waker: Waker) {
+        let mut wakers = self.lock();
+        if !wakers.iter().any(|registered| registered.will_wake(&waker)) {
+            wakers.push(waker);
+        }
+    }
+
+    pub(crate) fn wake_all(&self) {
+        let wakers = mem::take(&mut *self.lock());
+        debug!("Waking {} registered waiters", wakers.len());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    pub(crate) fn poll_shared<R>(&self, poll: impl FnOnce() -> R) -> R {
+        let Some(outer) = WAKER.with(|waker| waker.borrow().clone()) else {
+            return poll();
+        };
+
+        let scope = WakerScope::enter(Some(Waker {
+            target: WakeTarget::Set(self.clone()),
+        }));
+        let res = poll();
+        let registered = scope.registered();
+        drop(scope);
+
+        if registered {
+            self.register(outer);
+            WAKER_TAKEN.set(true);
+        }
+        res
+    }
+}
+
+struct WakerScope {
+    previous: Option<Waker>,
+    taken: bool,
+}
+
+impl WakerScope {
+    fn enter(waker: Option<Waker>) -> Self {
+        Self {
+            previous: WAKER.with(|current| current.replace(waker)),
+            taken: WAKER_TAKEN.replace(false),
+        }
+    }
+
+    fn registered(&self) -> bool {
+        WAKER_TAKEN.get()
+    }
+}
+
+impl Drop for WakerScope {
+    fn drop(&mut self) {
+        WAKER.with(|current| current.replace(self.previous.take()));
+        WAKER_TAKEN.set(self.taken);
+    }
+}
+
+pub fn spawn<F>(future: F) -> Result<(), FutError>
+where
+    F: Future<Error = FutError> + 'static,
+    F::Output: 'static,
+{
+    SPAWNED.with(|spawned| match spawned.borrow_mut().as_mut() {
+        Some(queue) => {
+            debug!("Deferring spawn until the current poll returns");
+            queue.push(TaskFuture::boxed(future));
+            Ok(())
+        }
+        None => Err(FutError::NoActiveRunner),
+    })
+}
+
+struct SpawnScope {
+    previous: Option<Vec<BoxedFuture>>,
+}
+
+impl SpawnScope {
+    fn enter() -> Self {
+        Self {
+            previous: SPAWNED.with(|spawned| spawned.replace(Some(Vec::new()))),
+        }
+    }
+
+    fn take(&self) -> Vec<BoxedFuture> {
+        SPAWNED.with(|spawned| {
+            spawned
+                .borrow_mut()
+                .as_mut()
+                .map(mem::take)
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl Drop for SpawnScope {
+    fn drop(&mut self) {
+        SPAWNED.with(|spawned| spawned.replace(self.previous.take()));
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownSignal {
+    triggered: Rc<Cell<bool>>,
+    waiters: WakerSet,
+}
+
+impl ShutdownSignal {
+    pub fn trigger(&self) {
+        if !self.triggered.replace(true) {
+            debug!("Shutdown signal triggered");
+            self.waiters.wake_all();
+        }
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        self.triggered.get()
+    }
+
+    fn reset(&self) {
+        self.triggered.set(false);
+    }
+
+    fn trigger_if_due(&self, deadline: &mut Option<Instant>) {
+        if deadline.is_some_and(|deadline| deadline <= Instant::now()) {
+            debug!("Shutdown deadline reached");
+            *deadline = None;
+            self.trigger();
+        }
+    }
+}
+
+impl Future for ShutdownSignal {
+    type Output = ();
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling ShutdownSignal future");
+        if self.is_triggered() {
+            return Ok(FutResult::finished(()));
+        }
+
+        match Waker::current() {
+            Some(waker) => {
+                self.waiters.register(waker);
+                Ok(FutResult {
+                    state: FutState::Waiting,
+                    value: None,
+                })
+            }
+            None => Ok(FutResult::pending()),
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying ShutdownSignal future");
+    }
+}
+
+fn park_until_due<'a>(
+    tasks: impl IntoIterator<Item = &'a Task>,
+    shutdown_at: Option<Instant>,
+    runner: &str,
+) {
+    let earliest = tasks
+        .into_iter()
+        .map(|task| task.wake_at)
+        .try_fold(None, |earliest: Option<Instant>, wake| {
+            wake.map(|wake| Some(earliest.map_or(wake, |e| e.min(wake))))
+        })
+        .flatten()
+        .map(|earliest| shutdown_at.map_or(earliest, |at| at.min(earliest)));
+
+    if let Some(deadline) = earliest {
+        let now = Instant::now();
+        if deadline > now {
+            debug!("{} parking for {:?}", runner, deadline - now);
+            thread::sleep(deadline - now);
+        }
+    }
+}
+
+const HOOK_TASK: TaskId = TaskId::MAX;
+
+fn run_hooks(hooks: &mut Vec<Hook>, phase: &str) -> Result<(), FutError> {
+    let wakes = Arc::new(WakeQueue::default());
+    let mut res = Ok(());
+    for mut hook in hooks.drain(..) {
+        debug!("Driving {} hook", phase);
+        let outcome = drive_hook(&mut hook, &wakes);
+        hook.cleanup();
+        if let Err(err) = outcome {
+            error!("ERROR: {} hook failed with {:?}", phase, err);
+            res = res.and(Err(err));
+        }
+    }
+
+    res
+}
+
+fn drive_hook(hook: &mut Hook, wakes: &Arc<WakeQueue>) -> Result<(), FutError> {
+    loop {
+        take_wake_at();
+        let waker_scope = WakerScope::enter(Some(Waker::task(HOOK_TASK, wakes)));
+        let res = hook.poll();
+        let registered = waker_scope.registered();
+        drop(waker_scope);
+
+        match res?.state {
+            FutState::Done => return Ok(()),
+            FutState::Pending => {}
+            FutState::Waiting => {
+                let deadline = take_wake_at();
+                if deadline.is_none() && !registered {
+                    return Err(FutError::SleepingUnsupported);
+                }
+                debug!("Parking hook until it is woken");
+                wakes.wait(deadline);
+                wakes.take();
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Priority {
+    High,
+    #[default]
+    Normal,
+    Low,
+}
+
+const STARVATION_TURNS: usize = 8;
+
+#[derive(Default)]
+struct ReadyQueues {
+    queues: [VecDeque<Task>; 3],
+    skipped: [usize; 3],
+}
+
+impl ReadyQueues {
+    fn push_back(&mut self, task: Task) {
+        self.queues[task.priority as usize].push_back(task);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.queues.iter().all(VecDeque::is_empty)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &Task> {
+        self.queues.iter().flatten()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        for queue in &mut self.queues {
+            queue.reserve(additional);
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.queues
+            .iter()
+            .map(VecDeque::capacity)
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn next_turn(&mut self, active: &mut VecDeque<Task>) {
+        let mut served = false;
+        for (level, queue) in self.queues.iter_mut().enumerate() {
+            if queue.is_empty() {
+                self.skipped[level] = 0;
+            } else if !served || self.skipped[level] >= STARVATION_TURNS {
+                if served {
+                    debug!("Promoting {} starved tasks", queue.len());
+                }
+                active.append(queue);
+                self.skipped[level] = 0;
+                served = true;
+            } else {
+                self.skipped[level] += 1;
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TaskInfo {
+    pub id: TaskId,
+    pub state: FutState,
+    pub age: Duration,
+}
+
+struct Task {
+    id: TaskId,
+    future: BoxedFuture,
+    scheduled_at: Instant,
+    wake_at: Option<Instant>,
+    parked: bool,
+    priority: Priority,
+}
+
+impl Task {
+    fn new(id: TaskId, future: BoxedFuture) -> Self {
+        Self {
+            id,
+            future,
+            scheduled_at: Instant::now(),
+            wake_at: None,
+            parked: false,
+            priority: Priority::Normal,
+        }
+    }
+
+    fn info(&self, state: FutState) -> TaskInfo {
+        TaskInfo {
+            id: self.id,
+            state,
+            age: self.scheduled_at.elapsed(),
+        }
+    }
+
+    fn poll(&mut self, strict: bool) -> Result<FutResult<AnyOutput>, FutError> {
+        take_violation();
+        let res = self.future.poll();
+        match take_violation() {
+            Some(violation) if strict => Err(FutError::ContractViolation {
+                task: self.id,
+                violation,
+            }),
+            Some(violation) => {
+                warn!(
+                    "Task {} violated the future contract: {:?}",
+                    self.id, violation
+                );
+                res
+            }
+            None => res,
+        }
+    }
+
+    fn cleanup(&mut self, results: &mut TaskResults, strict: bool) -> bool {
+        take_violation();
+        self.future.cleanup();
+        match take_violation() {
+            Some(violation) if strict => {
+                results.violate(self.id, violation);
+                true
+            }
+            Some(violation) => {
+                warn!(
+                    "Task {} violated the future contract: {:?}",
+                    self.id, violation
+                );
+                false
+            }
+            None => false,
+        }
+    }
+
+    fn complete(mut self, value: Option<AnyOutput>, results: &mut TaskResults, strict: bool) {
+        if self.cleanup(results, strict) {
+            return;
+        }
+        match value {
+            None if strict => results.violate(self.id, ContractViolation::DoneWithoutValue),
+            value => results.store(self.id, value),
+        }
+    }
+
+    fn fail(mut self, err: FutError, results: &mut TaskResults, strict: bool) {
+        if self.cleanup(results, strict) {
+            return;
+        }
+        let violation = match err {
+            FutError::ContractViolation { violation, .. } => violation,
+            FutError::PolledAfterCompletion => ContractViolation::PolledAfterCompletion,
+            FutError::CompletedWithoutValue if strict => ContractViolation::DoneWithoutValue,
+            err => {
+                warn!("Task {} failed with {:?}", self.id, err);
+                results.fail(self.id, err);
+                return;
+            }
+        };
+
+        if strict {
+            results.violate(self.id, violation);
+        } else {
+            warn!(
+                "Task {} violated the future contract: {:?}, terminating it",
+                self.id, violation
+            );
+            results.fail(self.id, err);
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SimpleRunner {
+    futs: VecDeque<Task>,
+    results: TaskResults,
+    next_id: TaskId,
+    strict: bool,
+    start_hooks: Vec<Hook>,
+    shutdown_hooks: Vec<Hook>,
+    shutdown: ShutdownSignal,
+    shutdown_after: Option<Duration>,
+    shutdown_at: Option<Instant>,
+    extensions: Extensions,
+    sizes: SizeReport,
+}
+
+impl SimpleRunner {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn insert_extension<T: 'static>(&mut self, value: T) -> Option<Rc<T>> {
+        debug!("Inserting {} extension", type_name::<T>());
+        self.extensions
+            .insert(TypeId::of::<T>(), Rc::new(value))
+            .and_then(|previous| previous.downcast::<T>().ok())
+    }
+
+    pub fn shutdown_signal(&self) -> ShutdownSignal {
+        self.shutdown.clone()
+    }
+
+    pub fn on_start<F>(&mut self, init: F)
+    where
+        F: Future<Output = (), Error = FutError> + 'static,
+    {
+        self.start_hooks.push(Box::new(init));
+    }
+
+    pub fn on_shutdown<F>(&mut self, teardown: F)
+    where
+        F: Future<Output = (), Error = FutError> + 'static,
+    {
+        self.shutdown_hooks.push(Box::new(teardown));
+    }
+
+    pub fn reserve(&mut self, tasks: usize) {
+        debug!("Reserving SimpleRunner capacity for {} tasks", tasks);
+        self.futs.reserve(tasks);
+        self.results.reserve(tasks);
+    }
+
+    pub fn capacity(&self) -> RunnerCapacity {
+        RunnerCapacity {
+            tasks: self.futs.capacity(),
+            results: self.results.capacity(),
+        }
+    }
+
+    pub fn with_strict_mode(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    pub fn with_shutdown_after(mut self, after: Duration) -> Self {
+        self.shutdown_after = Some(after);
+        self
+    }
+
+    pub fn with_size_report(mut self) -> Self {
+        self.sizes.enabled = true;
+        self
+    }
+
+    pub fn size_report(&self) -> Vec<FutureSize> {
+        self.sizes.report()
+    }
+}
+
+impl SimpleRunner {
+    pub fn find_tasks(&self, filter: impl Fn(&TaskInfo) -> bool) -> Vec<TaskInfo> {
+        let now = Instant::now();
+        self.futs
+            .iter()
+            .map(|task| match task.wake_at {
+                Some(wake) if wake > now => task.info(FutState::Waiting),
+                _ => task.info(FutState::Pending),
+            })
+            .filter(|info| filter(info))
+            .collect()
+    }
+
+    fn insert(&mut self, future: BoxedFuture) -> TaskId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.results.start(id);
+        self.futs.push_back(Task::new(id, future));
+        id
+    }
+}
+
+impl FutureRunner for SimpleRunner {
+    fn schedule<F>(&mut self, fut: F) -> JoinHandle<F::Output>
+    where
+        F: Future<Error = FutError> + 'static,
+        F::Output: 'static,
+    {
+        self.sizes.record::<F>();
+        let id = self.insert(TaskFuture::boxed(fut));
+        JoinHandle::new(id, &self.results)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.futs.is_empty()
+    }
+
+    fn run(&mut self) -> Result<(), FutError> {
+        let mut extensions = mem::take(&mut self.extensions);
+        let extension_scope = ExtensionScope::enter(&mut extensions);
+        let res = self.run_tasks();
+        drop(extension_scope);
+        self.extensions = extensions;
+        res
+    }
+
+    fn take_result<T: 'static>(&mut self, id: TaskId) -> Result<T, FutError> {
+        self.results.take(id)
+    }
+}
+
+impl SimpleRunner {
+    fn run_tasks(&mut self) -> Result<(), FutError> {
+        let spawn_scope = SpawnScope::enter();
+        self.shutdown.reset();
+        self.shutdown_at = self.shutdown_after.map(|after| Instant::now() + after);
+        let res = self.drive_tasks(&spawn_scope);
+        self.shutdown_at = None;
+        self.shutdown.trigger();
+        let shutdown = run_hooks(&mut self.shutdown_hooks, "shutdown");
+        res.and(shutdown)
+    }
+
+    fn drive_tasks(&mut self, spawn_scope: &SpawnScope) -> Result<(), FutError> {
+        run_hooks(&mut self.start_hooks, "start")?;
+        let _waker_scope = WakerScope::enter(None);
+        for future in spawn_scope.take() {
+            self.insert(future);
+        }
+
+        while !self.is_empty() {
+            park_until_due(&self.futs, self.shutdown_at, "SimpleRunner");
+            self.shutdown.trigger_if_due(&mut self.shutdown_at);
+
+            let mut i = 0;
+            while i < self.futs.len() {
+                if self.futs[i]
+                    .wake_at
+                    .is_some_and(|wake| wake > Instant::now())
+                {
+                    i += 1;
+                    continue;
+                }
+
+                take_wake_at();
+                match self.futs[i].poll(self.strict) {
+                    Ok(FutResult {
+                        state: FutState::Pending,
+                        ..
+                    }) => {
+                        self.futs[i].wake_at = None;
+                        i += 1;
+                    }
+                    Ok(FutResult {
+                        state: FutState::Waiting,
+                        ..
+                    }) => match take_wake_at() {
+                        Some(wake) => {
+                            self.futs[i].wake_at = Some(wake);
+                            i += 1;
+                        }
+                        None => return Err(FutError::SleepingUnsupported),
+                    },
+                    Ok(FutResult {
+                        state: FutState::Done,
+                        value,
+                    }) => {
+                        if let Some(task) = self.futs.remove(i) {
+                            task.complete(value, &mut self.results, self.strict);
+                        }
+                    }
+                    Err(e) => {
+                        if let Some(task) = self.futs.remove(i) {
+                            task.fail(e, &mut self.results, self.strict);
+                        }
+                    }
+                }
+
+                for future in spawn_scope.take() {
+                    self.insert(future);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct PollRunner {
+    active: VecDeque<Task>,
+    pending: ReadyQueues,
+    sleeping: VecDeque<Task>,
+    results: TaskResults,
+    next_id: TaskId,
+    strict: bool,
+    busy: bool,
+    idle_callback: Option<Box<dyn FnMut()>>,
+    busy_callback: Option<Box<dyn FnMut()>>,
+    start_hooks: Vec<Hook>,
+    shutdown_hooks: Vec<Hook>,
+    shutdown: ShutdownSignal,
+    shutdown_after: Option<Duration>,
+    shutdown_at: Option<Instant>,
+    extensions: Extensions,
+    sizes: SizeReport,
+    wakes: Arc<WakeQueue>,
+}
+
+impl PollRunner {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn insert_extension<T: 'static>(&mut self, value: T) -> Option<Rc<T>> {
+        debug!("Inserting {} extension", type_name::<T>());
+        self.extensions
+            .insert(TypeId::of::<T>(), Rc::new(value))
+            .and_then(|previous| previous.downcast::<T>().ok())
+    }
+
+    pub fn shutdown_signal(&self) -> ShutdownSignal {
+        self.shutdown.clone()
+    }
+
+    pub fn on_start<F>(&mut self, init: F)
+    where
+        F: Future<Output = (), Error = FutError> + 'static,
+    {
+        self.start_hooks.push(Box::new(init));
+    }
+
+    pub fn on_shutdown<F>(&mut self, teardown: F)
+    where
+        F: Future<Output = (), Error = FutError> + 'static,
+    {
+        self.shutdown_hooks.push(Box::new(teardown));
+    }
+
+    pub fn reserve(&mut self, tasks: usize) {
+        debug!("Reserving PollRunner capacity for {} tasks", tasks);
+        self.active.reserve(tasks);
+        self.pending.reserve(tasks);
+        self.sleeping.reserve(tasks);
+        self.results.reserve(tasks);
+    }
+
+    pub fn capacity(&self) -> RunnerCapacity {
+        RunnerCapacity {
+            tasks: self
+                .active
+                .capacity()
+                .min(self.pending.capacity())
+                .min(self.sleeping.capacity()),
+            results: self.results.capacity(),
+        }
+    }
+
+    pub fn with_strict_mode(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    pub fn with_shutdown_after(mut self, after: Duration) -> Self {
+        self.shutdown_after = Some(after);
+        self
+    }
+
+    pub fn with_size_report(mut self) -> Self {
+        self.sizes.enabled = true;
+        self
+    }
+
+    pub fn size_report(&self) -> Vec<FutureSize> {
+        self.sizes.report()
+    }
+
+    pub fn on_idle(&mut self, callback: impl FnMut() + 'static) {
+        self.idle_callback = Some(Box::new(callback));
+    }
+
+    pub fn on_busy(&mut self, callback: impl FnMut() + 'static) {
+        self.busy_callback = Some(Box::new(callback));
+    }
+
+    fn set_busy(&mut self, busy: bool) {
+        if self.busy == busy {
+            return;
+        }
+
+        self.busy = busy;
+        let callback = if busy {
+            debug!("PollRunner has runnable work");
+            &mut self.busy_callback
+        } else {
+            debug!("PollRunner is fully parked");
+            &mut self.idle_callback
+        };
+
+        if let Some(callback) = callback {
+            callback();
+        }
+    }
+
+    fn handle_sleeping_futures(&mut self) {
+        if self.sleeping.is_empty() {
+            return;
+        }
+
+        if self.pending.is_empty() {
+            self.park();
+        }
+        self.shutdown.trigger_if_due(&mut self.shutdown_at);
+
+        let woken = self.wakes.take();
+        let now = Instant::now();
+        let mut remaining = VecDeque::new();
+        while let Some(mut task) = self.sleeping.pop_front() {
+            let due =
+                woken.contains(&task.id) || task.wake_at.map_or(!task.parked, |wake| wake <= now);
+            if due {
+                task.wake_at = None;
+                task.parked = false;
+                self.pending.push_back(task);
+            } else {
+                remaining.push_back(task);
+            }
+        }
+
+        self.sleeping = remaining;
+    }
+
+    fn park(&self) {
+        let mut deadline = self.shutdown_at;
+        for task in &self.sleeping {
+            match task.wake_at {
+                Some(wake) => deadline = Some(deadline.map_or(wake, |d| d.min(wake))),
+                None if task.parked => {}
+                None => return,
+            }
+        }
+
+        match deadline {
+            Some(deadline) => debug!(
+                "PollRunner parking for up to {:?}",
+                deadline.saturating_duration_since(Instant::now())
+            ),
+            None => debug!("PollRunner parking until a task is woken"),
+        }
+        self.wakes.wait(deadline);
+    }
+}
+
+impl PollRunner {
+    pub fn find_tasks(&self, filter: impl Fn(&TaskInfo) -> bool) -> Vec<TaskInfo> {
+        let runnable = self
+            .active
+            .iter()
+            .chain(self.pending.iter())
+            .map(|task| task.info(FutState::Pending));
+        let sleeping = self
+            .sleeping
+            .iter()
+            .map(|task| task.info(FutState::Waiting));
+
+        runnable
+            .chain(sleeping)
+            .filter(|info| filter(info))
+            .collect()
+    }
+
+    pub fn schedule_with_priority<F>(&mut self, fut: F, priority: Priority) -> JoinHandle<F::Output>
+    where
+        F: Future<Error = FutError> + 'static,
+        F::Output: 'static,
+    {
+        self.sizes.record::<F>();
+        let id = self.insert_with_priority(TaskFuture::boxed(fut), priority);
+        JoinHandle::new(id, &self.results)
+    }
+
+    fn insert(&mut self, future: BoxedFuture) -> TaskId {
+        self.insert_with_priority(future, Priority::Normal)
+    }
+
+    fn insert_with_priority(&mut self, future: BoxedFuture, priority: Priority) -> TaskId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.results.start(id);
+        let mut task = Task::new(id, future);
+        task.priority = priority;
+        self.pending.push_back(task);
+        id
+    }
+}
+
+impl FutureRunner for PollRunner {
+    fn schedule<F>(&mut self, fut: F) -> JoinHandle<F::Output>
+    where
+        F: Future<Error = FutError> + 'static,
+        F::Output: 'static,
+    {
+        self.sizes.record::<F>();
+        let id = self.insert(TaskFuture::boxed(fut));
+        JoinHandle::new(id, &self.results)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.active.is_empty() && self.sleeping.is_empty() && self.pending.is_empty()
+    }
+
+    fn run(&mut self) -> Result<(), FutError> {
+        let mut extensions = mem::take(&mut self.extensions);
+        let extension_scope = ExtensionScope::enter(&mut extensions);
+        let res = self.run_tasks();
+        drop(extension_scope);
+        self.extensions = extensions;
+        res
+    }
+
+    fn take_result<T: 'static>(&mut self, id: TaskId) -> Result<T, FutError> {
+        self.results.take(id)
+    }
+}
+
+impl PollRunner {
+    fn run_tasks(&mut self) -> Result<(), FutError> {
+        let spawn_scope = SpawnScope::enter();
+        self.shutdown.reset();
+        self.shutdown_at = self.shutdown_after.map(|after| Instant::now() + after);
+        let res = self.drive_tasks(&spawn_scope);
+        self.set_busy(false);
+        self.shutdown_at = None;
+        self.shutdown.trigger();
+        let shutdown = run_hooks(&mut self.shutdown_hooks, "shutdown");
+        res.and(shutdown)
+    }
+
+    fn drive_tasks(&mut self, spawn_scope: &SpawnScope) -> Result<(), FutError> {
+        run_hooks(&mut self.start_hooks, "start")?;
+        for future in spawn_scope.take() {
+            self.insert(future);
+        }
+
+        while !self.is_empty() {
+            self.shutdown.trigger_if_due(&mut self.shutdown_at);
+            self.pending.next_turn(&mut self.active);
+            self.set_busy(!self.active.is_empty());
+
+            while let Some(mut task) = self.active.pop_front() {
+                take_wake_at();
+                let waker_scope = WakerScope::enter(Some(Waker::task(task.id, &self.wakes)));
+                let res = task.poll(self.strict);
+                let registered = waker_scope.registered();
+                drop(waker_scope);
+
+                match res {
+                    Ok(FutResult {
+                        state: FutState::Pending,
+                        ..
+                    }) => self.pending.push_back(task),
+                    Ok(FutResult {
+                        state: FutState::Waiting,
+                        value,
+                    }) => {
+                        task.wake_at = take_wake_at();
+                        task.parked = registered;
+                        if value.is_some() || task.wake_at.is_some() || task.parked {
+                            self.sleeping.push_back(task);
+                        } else {
+                            let err = if self.strict {
+                                FutError::ContractViolation {
+                                    task: task.id,
+                                    violation: ContractViolation::WaitingWithoutWakeSource,
+                                }
+                            } else {
+                                FutError::SleepingUnsupported
+                            };
+                            task.fail(err, &mut self.results, self.strict);
+                        }
+                    }
+                    Ok(FutResult {
+                        state: FutState::Done,
+                        value,
+                    }) => task.complete(value, &mut self.results, self.strict),
+                    Err(e) => task.fail(e, &mut self.results, self.strict),
+                }
+
+                for future in spawn_scope.take() {
+                    self.insert(future);
+                }
+            }
+
+            self.set_busy(!self.pending.is_empty());
+            self.handle_sleeping_futures();
+        }
+
+        Ok(())
+    }
+}
+
+pub fn init_logging() {
+    if SimpleLogger::new().init().is_err() {
+        debug!("Logger already initialized");
+    }
+}
+
+pub fn block_on<F>(future: F) -> Result<F::Output, FutError>
+where
+    F: Future<Error = FutError> + 'static,
+    F::Output: 'static,
+{
+    let mut runner = PollRunner::new();
+    let handle = runner.schedule(future);
+    runner.run()?;
+    handle.try_result()
+}
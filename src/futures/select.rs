@@ -0,0 +1,210 @@
+use crate::futures::{Either, FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::fmt::Debug;
+
+pub struct Select<F1, F2> {
+    inner: Option<(F1, F2)>,
+    winner: Option<Either<F1, F2>>,
+    biased: bool,
+    second_first: bool,
+}
+
+impl<F1, F2> Select<F1, F2>
+where
+    F1: Future,
+    F2: Future<Error = F1::Error>,
+{
+    pub fn new(first: F1, second: F2) -> Self {
+        debug!("Creating new Select future");
+        Self {
+            inner: Some((first, second)),
+            winner: None,
+            biased: false,
+            second_first: false,
+        }
+    }
+
+    pub fn biased(mut self) -> Self {
+        debug!("Select polling branches in declaration order");
+        self.biased = true;
+        self.second_first = false;
+        self
+    }
+}
+
+enum Branch<T> {
+    Won(T),
+    Running(FutState),
+    Empty,
+}
+
+fn poll_branch<F>(future: &mut F, branch: &str) -> Result<Branch<F::Output>, F::Error>
+where
+    F: Future,
+    F::Output: Debug,
+    F::Error: Debug,
+{
+    match future.poll() {
+        Ok(FutResult {
+            state: FutState::Done,
+            value: Some(value),
+        }) => {
+            debug!("Select {} future won with value {:?}", branch, value);
+            Ok(Branch::Won(value))
+        }
+        Ok(FutResult {
+            state: FutState::Done,
+            value: None,
+        }) => {
+            error!("ERROR: Select {} future completed without value!", branch);
+            Ok(Branch::Empty)
+        }
+        Ok(FutResult { state, .. }) => Ok(Branch::Running(state)),
+        Err(e) => {
+            error!("Select {} future poll resulted in error {:?}", branch, e);
+            Err(e)
+        }
+    }
+}
+
+impl<F1, F2> Future for Select<F1, F2>
+where
+    F1: Future,
+    F2: Future<Error = F1::Error>,
+    F1::Error: Debug + From<FutError>,
+    F1::Output: Debug,
+    F2::Output: Debug,
+{
+    type Output = Either<(F1::Output, F2), (F2::Output, F1)>;
+    type Error = F1::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Select future");
+        let Some((mut first, mut second)) = self.inner.take() else {
+            error!("ERROR: Select future polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        };
+
+        let order = if self.second_first { [1, 0] } else { [0, 1] };
+        if !self.biased {
+            self.second_first = !self.second_first;
+        }
+
+        let mut state = FutState::Done;
+        for branch in order {
+            let polled = match branch {
+                0 => poll_branch(&mut first, "first").map(|branch| match branch {
+                    Branch::Won(value) => Branch::Won(Either::Left(value)),
+                    Branch::Running(state) => Branch::Running(state),
+                    Branch::Empty => Branch::Empty,
+                }),
+                _ => poll_branch(&mut second, "second").map(|branch| match branch {
+                    Branch::Won(value) => Branch::Won(Either::Right(value)),
+                    Branch::Running(state) => Branch::Running(state),
+                    Branch::Empty => Branch::Empty,
+                }),
+            };
+
+            match polled {
+                Ok(Branch::Won(Either::Left(value))) => {
+                    self.winner = Some(Either::Left(first));
+                    return Ok(FutResult {
+                        state: FutState::Done,
+                        value: Some(Either::Left((value, second))),
+                    });
+                }
+                Ok(Branch::Won(Either::Right(value))) => {
+                    self.winner = Some(Either::Right(second));
+                    return Ok(FutResult {
+                        state: FutState::Done,
+                        value: Some(Either::Right((value, first))),
+                    });
+                }
+                Ok(Branch::Running(branch_state)) => state = state.combine(branch_state),
+                Ok(Branch::Empty) => {
+                    first.cleanup();
+                    second.cleanup();
+                    return Err(FutError::CompletedWithoutValue.into());
+                }
+                Err(e) => {
+                    self.inner = Some((first, second));
+                    return Err(e);
+                }
+            }
+        }
+
+        self.inner = Some((first, second));
+        Ok(FutResult { state, value: None })
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Select future");
+        if let Some((first, second)) = self.inner.as_mut() {
+            first.cleanup();
+            second.cleanup();
+        }
+        match self.winner.take() {
+            Some(Either::Left(mut winner)) => winner.cleanup(),
+            Some(Either::Right(mut winner)) => winner.cleanup(),
+            None => {}
+        }
+    }
+}
+
+pub struct Race<F1, F2> {
+    select: Select<F1, F2>,
+}
+
+impl<F1, F2> Race<F1, F2>
+where
+    F1: Future,
+    F2: Future<Output = F1::Output, Error = F1::Error>,
+{
+    pub fn new(first: F1, second: F2) -> Self {
+        debug!("Creating new Race future");
+        Self {
+            select: Select::new(first, second),
+        }
+    }
+
+    pub fn biased(self) -> Self {
+        Self {
+            select: self.select.biased(),
+        }
+    }
+}
+
+impl<F1, F2> Future for Race<F1, F2>
+where
+    F1: Future,
+    F2: Future<Output = F1::Output, Error = F1::Error>,
+    F1::Error: Debug + From<FutError>,
+    F1::Output: Debug,
+{
+    type Output = F1::Output;
+    type Error = F1::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Race future");
+        let FutResult { state, value } = self.select.poll()?;
+        let value = value.map(|winner| match winner {
+            Either::Left((value, mut loser)) => {
+                debug!("Race first future won, discarding the second");
+                loser.cleanup();
+                value
+            }
+            Either::Right((value, mut loser)) => {
+                debug!("Race second future won, discarding the first");
+                loser.cleanup();
+                value
+            }
+        });
+
+        Ok(FutResult { state, value })
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Race future");
+        self.select.cleanup();
+    }
+}
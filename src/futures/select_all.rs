@@ -0,0 +1,100 @@
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::{fmt::Debug, mem};
+
+pub struct SelectAll<F> {
+    futures: Vec<F>,
+    finished: bool,
+    biased: bool,
+    start: usize,
+}
+
+pub fn select_all<F: Future>(futures: Vec<F>) -> SelectAll<F> {
+    debug!(
+        "Creating new SelectAll future over {} futures",
+        futures.len()
+    );
+    SelectAll {
+        futures,
+        finished: false,
+        biased: false,
+        start: 0,
+    }
+}
+
+impl<F> SelectAll<F> {
+    pub fn biased(mut self) -> Self {
+        debug!("SelectAll polling futures in declaration order");
+        self.biased = true;
+        self.start = 0;
+        self
+    }
+}
+
+impl<F> Future for SelectAll<F>
+where
+    F: Future,
+    F::Output: Debug,
+    F::Error: Debug + From<FutError>,
+{
+    type Output = (F::Output, usize, Vec<F>);
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling SelectAll future");
+        if self.finished {
+            error!("ERROR: SelectAll future polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        }
+
+        if self.futures.is_empty() {
+            error!("ERROR: SelectAll future has nothing to select from!");
+            return Err(FutError::CompletedWithoutValue.into());
+        }
+
+        let len = self.futures.len();
+        let start = self.start % len;
+        if !self.biased {
+            self.start = start + 1;
+        }
+
+        let mut state = FutState::Done;
+        for index in (start..len).chain(0..start) {
+            match self.futures[index].poll()? {
+                FutResult {
+                    state: FutState::Done,
+                    value: Some(value),
+                } => {
+                    debug!("SelectAll future {} won with value {:?}", index, value);
+                    self.finished = true;
+                    let mut remaining = mem::take(&mut self.futures);
+                    remaining.remove(index).cleanup();
+
+                    return Ok(FutResult {
+                        state: FutState::Done,
+                        value: Some((value, index, remaining)),
+                    });
+                }
+                FutResult {
+                    state: FutState::Done,
+                    value: None,
+                } => {
+                    error!("ERROR: SelectAll future {} completed without value!", index);
+                    return Err(FutError::CompletedWithoutValue.into());
+                }
+                FutResult {
+                    state: child_state, ..
+                } => state = state.combine(child_state),
+            }
+        }
+
+        Ok(FutResult { state, value: None })
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying SelectAll future");
+        for future in self.futures.iter_mut() {
+            future.cleanup();
+        }
+    }
+}
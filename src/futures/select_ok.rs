@@ -0,0 +1,88 @@
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error, warn};
+use std::fmt::Debug;
+
+pub struct SelectOk<F> {
+    futures: Vec<F>,
+    finished: bool,
+}
+
+pub fn select_ok<F: Future>(futures: Vec<F>) -> SelectOk<F> {
+    debug!(
+        "Creating new SelectOk future over {} futures",
+        futures.len()
+    );
+    SelectOk {
+        futures,
+        finished: false,
+    }
+}
+
+impl<F> Future for SelectOk<F>
+where
+    F: Future,
+    F::Output: Debug,
+    F::Error: Debug + From<FutError>,
+{
+    type Output = F::Output;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling SelectOk future");
+        if self.finished {
+            error!("ERROR: SelectOk future polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        }
+
+        if self.futures.is_empty() {
+            error!("ERROR: SelectOk future has nothing to select from!");
+            self.finished = true;
+            return Err(FutError::CompletedWithoutValue.into());
+        }
+
+        let mut state = FutState::Done;
+        let mut index = 0;
+        while index < self.futures.len() {
+            let err = match self.futures[index].poll() {
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: Some(value),
+                }) => {
+                    debug!("SelectOk future {} succeeded with value {:?}", index, value);
+                    self.finished = true;
+                    self.cleanup();
+                    return Ok(FutResult::finished(value));
+                }
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: None,
+                }) => FutError::CompletedWithoutValue.into(),
+                Ok(FutResult {
+                    state: child_state, ..
+                }) => {
+                    state = state.combine(child_state);
+                    index += 1;
+                    continue;
+                }
+                Err(e) => e,
+            };
+
+            warn!("SelectOk future {} failed with {:?}", index, err);
+            self.futures.remove(index).cleanup();
+            if self.futures.is_empty() {
+                error!("SelectOk exhausted every future");
+                self.finished = true;
+                return Err(err);
+            }
+        }
+
+        Ok(FutResult { state, value: None })
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying SelectOk future");
+        for mut future in self.futures.drain(..) {
+            future.cleanup();
+        }
+    }
+}
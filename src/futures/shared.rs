@@ -0,0 +1,146 @@
+use crate::futures::runtime::WakerSet;
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+struct Inner<F: Future> {
+    future: Option<F>,
+    result: Option<Result<F::Output, F::Error>>,
+    waiters: WakerSet,
+}
+
+pub struct Shared<F: Future> {
+    inner: Rc<RefCell<Inner<F>>>,
+    finished: bool,
+}
+
+impl<F: Future> Shared<F> {
+    pub fn new(future: F) -> Self {
+        debug!("Creating new Shared future");
+        Self {
+            inner: Rc::new(RefCell::new(Inner {
+                future: Some(future),
+                result: None,
+                waiters: WakerSet::default(),
+            })),
+            finished: false,
+        }
+    }
+
+    pub fn is_settled(&self) -> bool {
+        self.inner.borrow().result.is_some()
+    }
+
+    pub(crate) fn is_failed(&self) -> bool {
+        matches!(self.inner.borrow().result, Some(Err(_)))
+    }
+
+    pub fn downgrade(&self) -> WeakShared<F> {
+        debug!("Downgrading Shared future handle");
+        WeakShared {
+            inner: Rc::downgrade(&self.inner),
+        }
+    }
+}
+
+pub struct WeakShared<F: Future> {
+    inner: Weak<RefCell<Inner<F>>>,
+}
+
+impl<F: Future> WeakShared<F> {
+    pub fn upgrade(&self) -> Option<Shared<F>> {
+        let inner = self.inner.upgrade()?;
+        debug!("Upgrading WeakShared future handle");
+        Some(Shared {
+            inner,
+            finished: false,
+        })
+    }
+}
+
+impl<F: Future> Clone for WeakShared<F> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Weak::clone(&self.inner),
+        }
+    }
+}
+
+impl<F: Future> Clone for Shared<F> {
+    fn clone(&self) -> Self {
+        debug!("Cloning Shared future handle");
+        Self {
+            inner: Rc::clone(&self.inner),
+            finished: false,
+        }
+    }
+}
+
+impl<F> Future for Shared<F>
+where
+    F: Future,
+    F::Output: Clone,
+    F::Error: Clone + From<FutError>,
+{
+    type Output = F::Output;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Shared future");
+        if self.finished {
+            error!("ERROR: Shared future handle polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        }
+
+        let mut inner = self.inner.borrow_mut();
+        if inner.result.is_none() {
+            let Inner {
+                future, waiters, ..
+            } = &mut *inner;
+            let Some(future) = future.as_mut() else {
+                return Err(FutError::CompletedWithoutValue.into());
+            };
+
+            let result = match waiters.poll_shared(|| future.poll()) {
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: Some(value),
+                }) => Ok(value),
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: None,
+                }) => Err(FutError::CompletedWithoutValue.into()),
+                Ok(FutResult { state, .. }) => return Ok(FutResult { state, value: None }),
+                Err(e) => Err(e),
+            };
+
+            debug!("Shared inner future settled, caching its result");
+            future.cleanup();
+            inner.future = None;
+            inner.result = Some(result);
+            inner.waiters.wake_all();
+        }
+
+        self.finished = true;
+        match inner.result.as_ref() {
+            Some(Ok(value)) => Ok(FutResult {
+                state: FutState::Done,
+                value: Some(value.clone()),
+            }),
+            Some(Err(e)) => Err(e.clone()),
+            None => Err(FutError::CompletedWithoutValue.into()),
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Shared future handle");
+        if Rc::strong_count(&self.inner) > 1 {
+            return;
+        }
+
+        if let Some(future) = self.inner.borrow_mut().future.as_mut() {
+            future.cleanup();
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::futures::{BoxFuture, FutError, Future, Shared};
+use log::debug;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::rc::Rc;
+
+type Flight<V> = Shared<BoxFuture<V, FutError>>;
+
+pub struct SingleFlight<K, V> {
+    in_flight: Rc<RefCell<HashMap<K, Flight<V>>>>,
+}
+
+impl<K, V> SingleFlight<K, V>
+where
+    K: Eq + Hash + Debug,
+    V: 'static,
+{
+    pub fn new() -> Self {
+        debug!("Creating new SingleFlight group");
+        Self {
+            in_flight: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    pub fn get<F, Fn>(&self, key: K, make: Fn) -> Flight<V>
+    where
+        F: Future<Output = V, Error = FutError> + 'static,
+        Fn: FnOnce() -> F,
+    {
+        let mut in_flight = self.in_flight.borrow_mut();
+        in_flight.retain(|_, shared| !shared.is_settled());
+
+        if let Some(shared) = in_flight.get(&key) {
+            debug!("SingleFlight joining in-flight request for {:?}", key);
+            return shared.clone();
+        }
+
+        debug!("SingleFlight starting request for {:?}", key);
+        let shared = Shared::new(Box::new(make()) as BoxFuture<V, FutError>);
+        in_flight.insert(key, shared.clone());
+        shared
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+            .borrow()
+            .values()
+            .filter(|shared| !shared.is_settled())
+            .count()
+    }
+}
+
+impl<K, V> Default for SingleFlight<K, V>
+where
+    K: Eq + Hash + Debug,
+    V: 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Clone for SingleFlight<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            in_flight: Rc::clone(&self.in_flight),
+        }
+    }
+}
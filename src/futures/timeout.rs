@@ -0,0 +1,72 @@
+use crate::futures::runtime::wake_at;
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub struct Timeout<F> {
+    future: Option<F>,
+    deadline: Instant,
+}
+
+impl<F> Timeout<F> {
+    pub fn new(future: F, duration: Duration) -> Self {
+        debug!("Creating new Timeout future with duration {:?}", duration);
+        Self {
+            future: Some(future),
+            deadline: Instant::now() + duration,
+        }
+    }
+}
+
+impl<F> Future for Timeout<F>
+where
+    F: Future,
+    F::Error: From<FutError>,
+{
+    type Output = F::Output;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Timeout future");
+        let Some(future) = self.future.as_mut() else {
+            error!("ERROR: Timeout future polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        };
+
+        let res = match future.poll() {
+            Ok(res) => res,
+            Err(e) => {
+                self.cleanup();
+                return Err(e);
+            }
+        };
+
+        if res.state == FutState::Done {
+            self.cleanup();
+            return Ok(res);
+        }
+
+        if Instant::now() >= self.deadline {
+            debug!("Timeout future deadline passed");
+            self.cleanup();
+            return Err(FutError::TimedOut.into());
+        }
+
+        if res.state == FutState::Waiting {
+            wake_at(self.deadline);
+        }
+
+        Ok(FutResult {
+            state: res.state,
+            value: None,
+        })
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Timeout future");
+        if let Some(mut future) = self.future.take() {
+            future.cleanup();
+        }
+    }
+}
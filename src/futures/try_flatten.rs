@@ -0,0 +1,105 @@
+use crate::futures::{FutError, FutResult, FutState, Future};
+use log::{debug, error};
+use std::fmt::Debug;
+use std::mem;
+
+enum TryFlattenState<F1, F2> {
+    First(F1),
+    Second(F2),
+    Done,
+}
+
+pub struct TryFlatten<F1, F2> {
+    state: TryFlattenState<F1, F2>,
+}
+
+impl<F1, F2, E> TryFlatten<F1, F2>
+where
+    F1: Future<Output = Result<F2, E>>,
+    F2: Future<Error = F1::Error>,
+    E: Into<F1::Error>,
+{
+    pub fn new(future: F1) -> Self {
+        debug!("Creating new TryFlatten future");
+        Self {
+            state: TryFlattenState::First(future),
+        }
+    }
+}
+
+impl<F1, F2, E> Future for TryFlatten<F1, F2>
+where
+    F1: Future<Output = Result<F2, E>>,
+    F2: Future<Error = F1::Error>,
+    F1::Error: Debug + From<FutError>,
+    E: Into<F1::Error>,
+{
+    type Output = F2::Output;
+    type Error = F1::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling TryFlatten future");
+        match mem::replace(&mut self.state, TryFlattenState::Done) {
+            TryFlattenState::First(mut future) => match future.poll()? {
+                FutResult {
+                    state: FutState::Done,
+                    value: Some(Ok(next)),
+                } => {
+                    debug!("TryFlatten outer future produced the inner future");
+                    future.cleanup();
+                    self.state = TryFlattenState::Second(next);
+                    Ok(FutResult {
+                        state: FutState::Pending,
+                        value: None,
+                    })
+                }
+                FutResult {
+                    state: FutState::Done,
+                    value: Some(Err(e)),
+                } => {
+                    let err = e.into();
+                    error!("TryFlatten outer future resolved to error {:?}", err);
+                    future.cleanup();
+                    Err(err)
+                }
+                FutResult {
+                    state: FutState::Done,
+                    value: None,
+                } => {
+                    error!("ERROR: TryFlatten outer future completed without value!");
+                    future.cleanup();
+                    Err(FutError::CompletedWithoutValue.into())
+                }
+                FutResult { state, .. } => {
+                    self.state = TryFlattenState::First(future);
+                    Ok(FutResult { state, value: None })
+                }
+            },
+            TryFlattenState::Second(mut future) => {
+                let res = future.poll();
+                match res {
+                    Ok(FutResult {
+                        state: FutState::Done,
+                        ..
+                    })
+                    | Err(_) => future.cleanup(),
+                    Ok(_) => self.state = TryFlattenState::Second(future),
+                }
+                res
+            }
+            TryFlattenState::Done => {
+                error!("ERROR: TryFlatten future polled after completion!");
+                Err(FutError::PolledAfterCompletion.into())
+            }
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying TryFlatten future");
+        match mem::replace(&mut self.state, TryFlattenState::Done) {
+            TryFlattenState::First(mut future) => future.cleanup(),
+            TryFlattenState::Second(mut future) => future.cleanup(),
+            TryFlattenState::Done => {}
+        }
+    }
+}
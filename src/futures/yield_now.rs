@@ -0,0 +1,42 @@
+use crate::futures::{FutError, FutResult, Future};
+use log::{debug, error};
+
+#[derive(Debug, Clone, Copy)]
+pub struct YieldNow {
+    yielded: bool,
+    finished: bool,
+}
+
+pub fn yield_now() -> YieldNow {
+    debug!("Creating new YieldNow future");
+    YieldNow {
+        yielded: false,
+        finished: false,
+    }
+}
+
+impl Future for YieldNow {
+    type Output = ();
+    type Error = FutError;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling YieldNow future");
+        if self.finished {
+            error!("ERROR: YieldNow future polled after completion!");
+            return Err(FutError::PolledAfterCompletion);
+        }
+
+        if !self.yielded {
+            debug!("YieldNow giving up its turn");
+            self.yielded = true;
+            return Ok(FutResult::pending());
+        }
+
+        self.finished = true;
+        Ok(FutResult::finished(()))
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying YieldNow future");
+    }
+}
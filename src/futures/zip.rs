@@ -0,0 +1,74 @@
+use crate::futures::{FutError, FutResult, FutState, Future, MaybeDone};
+use log::{debug, error};
+
+pub struct Zip<A: Future, B: Future> {
+    left: MaybeDone<A>,
+    right: MaybeDone<B>,
+    finished: bool,
+}
+
+pub fn zip<A, B>(left: A, right: B) -> Zip<A, B>
+where
+    A: Future,
+    B: Future<Error = A::Error>,
+{
+    debug!("Creating new Zip future");
+    Zip {
+        left: MaybeDone::Future(left),
+        right: MaybeDone::Future(right),
+        finished: false,
+    }
+}
+
+impl<A, B> Future for Zip<A, B>
+where
+    A: Future,
+    B: Future<Error = A::Error>,
+    A::Error: From<FutError>,
+{
+    type Output = (A::Output, B::Output);
+    type Error = A::Error;
+
+    fn poll(&mut self) -> Result<FutResult<Self::Output>, Self::Error> {
+        debug!("Polling Zip future");
+        if self.finished {
+            error!("ERROR: Zip future polled after completion!");
+            return Err(FutError::PolledAfterCompletion.into());
+        }
+
+        let left = self.left.poll();
+        let right = self.right.poll();
+        let state = match (left, right) {
+            (Ok(left), Ok(right)) => left.state.combine(right.state),
+            (Err(e), _) | (_, Err(e)) => {
+                self.finished = true;
+                self.cleanup();
+                return Err(e);
+            }
+        };
+
+        if state != FutState::Done {
+            if self.left.is_done() != self.right.is_done() {
+                debug!("Zip buffering the side that finished first");
+            }
+            return Ok(FutResult { state, value: None });
+        }
+
+        match (self.left.take_output(), self.right.take_output()) {
+            (Some(left), Some(right)) => {
+                self.finished = true;
+                Ok(FutResult {
+                    state: FutState::Done,
+                    value: Some((left, right)),
+                })
+            }
+            _ => Err(FutError::CompletedWithoutValue.into()),
+        }
+    }
+
+    fn cleanup(&mut self) {
+        debug!("Destroying Zip future");
+        self.left.cancel();
+        self.right.cancel();
+    }
+}
@@ -0,0 +1,4 @@
+mod futures;
+
+pub use futures::*;
+pub use futures_macros::{main, test};
@@ -0,0 +1,22 @@
+use futures::runtime::spawn;
+use futures::{lazy, spawn_blocking, Delay, Done, FutError, Future, FutureExt};
+use std::time::Duration;
+
+#[futures::test]
+fn resolves_chained_future() -> impl Future<Output = (), Error = FutError> {
+    Done::new(6)
+        .then(|x| Done::new(x * 7))
+        .map(|x| assert_eq!(x, 42))
+}
+
+#[futures::test]
+fn parks_on_delay_and_blocking_pool() -> impl Future<Output = (), Error = FutError> {
+    Delay::new(Duration::from_millis(5))
+        .then(|()| spawn_blocking(|| 5))
+        .map(|x| assert_eq!(x, 5))
+}
+
+#[futures::test]
+fn spawns_onto_the_ambient_runner() -> impl Future<Output = (), Error = FutError> {
+    lazy(|| spawn(Done::new(1))).map(|spawned| assert!(spawned.is_ok()))
+}